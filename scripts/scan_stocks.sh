@@ -17,27 +17,9 @@ STOCKS=$(IFS=,; echo "$*")
 
 echo "Scanning stocks: $STOCKS"
 
-# Create temporary environment file
-TEMP_ENV=$(mktemp)
-cat ../.env > "$TEMP_ENV"
-
-# Update the stock list
-if grep -q "NASDAQ_STOCKS=" "$TEMP_ENV"; then
-    sed -i "s/^NASDAQ_STOCKS=.*/NASDAQ_STOCKS=$STOCKS/" "$TEMP_ENV"
-else
-    echo "NASDAQ_STOCKS=$STOCKS" >> "$TEMP_ENV"
-fi
-
-# Set the temporary environment file
-export ENV_FILE="$TEMP_ENV"
-
 echo ""
-echo "Starting scanner with custom stock list..."
-echo "Press Ctrl+C to stop"
-
-# Run the scanner
-cd ..
-cargo run
+echo "Running a single scan with custom stock list..."
 
-# Clean up
-rm -f "$TEMP_ENV"
+# Run the scanner from the project root so ./.env is picked up
+cd "$(dirname "$0")/.."
+cargo run -- scan --once --symbols "$STOCKS"
@@ -0,0 +1,246 @@
+use crate::api::IbkrError;
+use chrono::NaiveDate;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResolvedContract {
+    pub symbol: String,
+    pub conid: i64,
+    pub exchange: String,
+    pub company_name: String,
+    // Option months from the same search, refreshed once `months_as_of` is no longer today
+    #[serde(default)]
+    pub option_months: Vec<String>,
+    #[serde(default)]
+    pub months_as_of: Option<NaiveDate>,
+}
+
+// Symbol -> contract mapping persisted as JSON between runs
+#[derive(Debug, Default)]
+pub struct ConidCache {
+    path: Option<PathBuf>,
+    entries: HashMap<String, ResolvedContract>,
+}
+
+impl ConidCache {
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    pub fn load(path: &str) -> Self {
+        let path = PathBuf::from(path);
+
+        let entries = match fs::read_to_string(&path) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    warn!("Ignoring corrupt conid cache {}: {}", path.display(), e);
+                    HashMap::new()
+                }
+            },
+            Err(_) => HashMap::new(),
+        };
+
+        info!("Loaded {} cached contract IDs from {}", entries.len(), path.display());
+
+        Self {
+            path: Some(path),
+            entries,
+        }
+    }
+
+    pub fn get(&self, symbol: &str) -> Option<&ResolvedContract> {
+        self.entries.get(&symbol.to_uppercase())
+    }
+
+    pub fn insert(&mut self, contract: ResolvedContract) {
+        self.entries.insert(contract.symbol.to_uppercase(), contract);
+
+        if let Some(path) = &self.path {
+            if let Err(e) = Self::persist(path, &self.entries) {
+                warn!("Failed to write conid cache {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    fn persist(path: &Path, entries: &HashMap<String, ResolvedContract>) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let json = serde_json::to_string_pretty(entries).map_err(std::io::Error::other)?;
+        fs::write(path, json)
+    }
+}
+
+// Pick the NASDAQ-listed stock contract out of a /iserver/secdef/search response
+pub fn select_stock_contract(symbol: &str, json: &Value) -> Result<ResolvedContract, IbkrError> {
+    let results = json.as_array().ok_or_else(|| {
+        IbkrError::DataError(format!("Unexpected secdef/search response for {}", symbol))
+    })?;
+
+    let candidates: Vec<ResolvedContract> = results
+        .iter()
+        .filter(|entry| {
+            entry
+                .get("symbol")
+                .and_then(Value::as_str)
+                .is_some_and(|s| s.eq_ignore_ascii_case(symbol))
+        })
+        .filter(|entry| has_stock_section(entry))
+        .filter_map(|entry| {
+            Some(ResolvedContract {
+                symbol: symbol.to_uppercase(),
+                conid: parse_conid(entry.get("conid")?)?,
+                exchange: entry
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                company_name: entry
+                    .get("companyName")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                option_months: Vec::new(),
+                months_as_of: None,
+            })
+        })
+        .collect();
+
+    let mut nasdaq: Vec<&ResolvedContract> = candidates
+        .iter()
+        .filter(|c| c.exchange.eq_ignore_ascii_case("NASDAQ"))
+        .collect();
+
+    match (nasdaq.len(), candidates.len()) {
+        (1, _) => Ok(nasdaq.remove(0).clone()),
+        (0, 0) => Err(IbkrError::UnknownSymbol(symbol.to_string())),
+        (0, 1) => Ok(candidates[0].clone()),
+        _ => Err(IbkrError::AmbiguousSymbol {
+            symbol: symbol.to_string(),
+            candidates: candidates
+                .iter()
+                .map(|c| format!("{} ({}, conid {})", c.company_name, c.exchange, c.conid))
+                .collect(),
+        }),
+    }
+}
+
+fn has_stock_section(entry: &Value) -> bool {
+    entry
+        .get("sections")
+        .and_then(Value::as_array)
+        .is_some_and(|sections| {
+            sections
+                .iter()
+                .any(|s| s.get("secType").and_then(Value::as_str) == Some("STK"))
+        })
+}
+
+// IBKR returns conids as either numbers or strings depending on the endpoint
+pub fn parse_conid(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn search_entry(conid: &str, symbol: &str, exchange: &str) -> Value {
+        json!({
+            "conid": conid,
+            "companyName": format!("{} INC", symbol),
+            "symbol": symbol,
+            "description": exchange,
+            "sections": [{"secType": "STK"}, {"secType": "OPT", "months": "JAN25;FEB25"}]
+        })
+    }
+
+    #[test]
+    fn test_selects_nasdaq_listing() {
+        let json = json!([
+            search_entry("12345", "AAPL", "MEXI"),
+            search_entry("265598", "AAPL", "NASDAQ"),
+        ]);
+
+        let contract = select_stock_contract("aapl", &json).unwrap();
+        assert_eq!(contract.conid, 265598);
+        assert_eq!(contract.symbol, "AAPL");
+    }
+
+    #[test]
+    fn test_unknown_symbol() {
+        let json = json!([search_entry("1", "MSFT", "NASDAQ")]);
+
+        assert!(matches!(
+            select_stock_contract("ZZZZ", &json),
+            Err(IbkrError::UnknownSymbol(_))
+        ));
+    }
+
+    #[test]
+    fn test_ambiguous_symbol() {
+        let json = json!([
+            search_entry("1", "ABC", "NYSE"),
+            search_entry("2", "ABC", "LSE"),
+        ]);
+
+        assert!(matches!(
+            select_stock_contract("ABC", &json),
+            Err(IbkrError::AmbiguousSymbol { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conids.json");
+        let path = path.to_str().unwrap();
+
+        let mut cache = ConidCache::load(path);
+        cache.insert(ResolvedContract {
+            symbol: "MSFT".to_string(),
+            conid: 272093,
+            exchange: "NASDAQ".to_string(),
+            company_name: "MICROSOFT CORP".to_string(),
+            option_months: vec!["JAN25".to_string()],
+            months_as_of: NaiveDate::from_ymd_opt(2025, 1, 2),
+        });
+
+        let reloaded = ConidCache::load(path);
+        assert_eq!(reloaded.get("msft").map(|c| c.conid), Some(272093));
+        assert_eq!(reloaded.get("msft").unwrap().option_months, vec!["JAN25"]);
+    }
+
+    #[test]
+    fn test_cache_without_months_still_loads() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conids.json");
+        fs::write(
+            &path,
+            r#"{"AAPL": {"symbol": "AAPL", "conid": 265598, "exchange": "NASDAQ",
+                "company_name": "APPLE INC"}}"#,
+        )
+        .unwrap();
+
+        let cache = ConidCache::load(path.to_str().unwrap());
+        let contract = cache.get("AAPL").unwrap();
+
+        assert_eq!(contract.conid, 265598);
+        assert!(contract.option_months.is_empty());
+        assert_eq!(contract.months_as_of, None);
+    }
+}
@@ -1,15 +1,34 @@
+use crate::api::contract_resolver::{select_stock_contract, ConidCache};
+use crate::api::rate_limiter::RateLimiter;
+use crate::api::retry::RetryPolicy;
+use crate::api::session::AuthStatus;
+use crate::api::market_data::{
+    parse_snapshots, MarketSnapshot, UnderlyingQuote, OPTION_FIELDS, UNDERLYING_FIELDS,
+};
+use crate::api::secdef::{parse_option_contracts, parse_option_months, parse_strikes, strikes_near};
 use crate::models::{OptionChain, OptionType};
+use chrono::Utc;
 use reqwest::{Client, Response};
 use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::time::sleep;
 use thiserror::Error;
-use log::{error, info, warn};
+use log::{debug, error, info, warn};
+
+const SNAPSHOT_BATCH_SIZE: usize = 50;
+const SNAPSHOT_POLL_ATTEMPTS: u32 = 3;
+const SNAPSHOT_POLL_DELAY: Duration = Duration::from_millis(500);
 
 #[derive(Debug, Clone)]
 pub struct IbkrClient {
     client: Client,
     base_url: String,
     account_id: String,
+    conid_cache: Arc<Mutex<ConidCache>>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Arc<RateLimiter>,
 }
 
 #[derive(Debug, Error)]
@@ -18,10 +37,43 @@ pub enum IbkrError {
     RequestError(#[from] reqwest::Error),
     #[error("API error: {0}")]
     ApiError(String),
+    #[error("API request failed with status: {status}")]
+    HttpStatus {
+        status: u16,
+        retry_after: Option<Duration>,
+    },
+    #[error("Request failed after {attempts} attempts: {source}")]
+    RetriesExhausted {
+        attempts: u32,
+        source: Box<IbkrError>,
+    },
     #[error("JSON parsing error: {0}")]
     JsonError(#[from] serde_json::Error),
     #[error("Invalid data format: {0}")]
     DataError(String),
+    #[error("Unknown symbol: {0}")]
+    UnknownSymbol(String),
+    #[error("Ambiguous symbol {symbol}, candidates: {}", candidates.join(", "))]
+    AmbiguousSymbol {
+        symbol: String,
+        candidates: Vec<String>,
+    },
+}
+
+impl IbkrError {
+    // Timeouts, refused or reset connections (while connecting or mid-response), 429 and 5xx
+    // are worth another attempt. A bad URL or an undecodable body won't change on retry.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            IbkrError::RequestError(e) => {
+                !e.is_builder()
+                    && !e.is_decode()
+                    && (e.is_timeout() || e.is_connect() || e.is_request() || e.is_body())
+            }
+            IbkrError::HttpStatus { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
 }
 
 impl IbkrClient {
@@ -35,67 +87,329 @@ impl IbkrClient {
             client,
             base_url,
             account_id,
+            conid_cache: Arc::new(Mutex::new(ConidCache::in_memory())),
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: Arc::new(RateLimiter::default()),
+        }
+    }
+    
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Arc::new(limiter);
+        self
+    }
+    
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+    
+    pub fn with_conid_cache(mut self, cache: ConidCache) -> Self {
+        self.conid_cache = Arc::new(Mutex::new(cache));
+        self
+    }
+    
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+    
+    pub async fn resolve_conid(&self, symbol: &str) -> Result<i64, IbkrError> {
+        if let Some(contract) = self.conid_cache.lock().unwrap().get(symbol) {
+            return Ok(contract.conid);
         }
+        
+        info!("Resolving contract ID for {}", symbol);
+        
+        let json = self.search_contracts(symbol).await?;
+        let mut contract = select_stock_contract(symbol, &json)?;
+        let conid = contract.conid;
+        
+        // The search already lists the option months, so the chain needn't search again
+        contract.option_months = parse_option_months(&json, conid);
+        contract.months_as_of = Some(Utc::now().date_naive());
+        
+        info!("Resolved {} to conid {} ({})", symbol, conid, contract.exchange);
+        self.conid_cache.lock().unwrap().insert(contract);
+        
+        Ok(conid)
     }
     
     pub async fn get_option_chain(
         &self,
         symbol: &str,
+        underlying_price: f64,
         strike_count: usize,
+        max_months: usize,
+        rights: &[OptionType],
     ) -> Result<Vec<OptionChain>, IbkrError> {
         info!("Fetching option chain for {}", symbol);
         
-        // In a real implementation, this would call the actual IBKR API
-        // For now, we'll simulate the response format
-        let url = format!("{}/v1/api/iserver/secdef/info", self.base_url);
+        let conid = self.resolve_conid(symbol).await?;
+        let months = self.get_option_months(symbol, conid).await?;
+        
+        if months.is_empty() {
+            return Err(IbkrError::DataError(format!("No option months listed for {}", symbol)));
+        }
+        
+        let mut chains = Vec::new();
+        
+        for month in months.iter().take(max_months) {
+            let (calls, puts) = self.get_strikes(conid, month).await?;
+            
+            for (option_type, strikes) in [(OptionType::Call, calls), (OptionType::Put, puts)] {
+                if !rights.contains(&option_type) {
+                    continue;
+                }
+                
+                let right = match option_type {
+                    OptionType::Call => "C",
+                    OptionType::Put => "P",
+                };
+                
+                let wanted = strikes_near(&strikes, underlying_price, strike_count);
+                if wanted.is_empty() {
+                    continue;
+                }
+                
+                match self.get_month_contracts(symbol, conid, month, right, &wanted).await {
+                    Ok(mut contracts) => chains.append(&mut contracts),
+                    Err(e) => warn!("Failed to resolve {} {} {}: {}", symbol, month, right, e),
+                }
+            }
+        }
+        
+        info!("Discovered {} option contracts for {}", chains.len(), symbol);
+        
+        Ok(chains)
+    }
+    
+    // Months cached by resolve_conid today are reused; otherwise search once and cache them
+    pub async fn get_option_months(&self, symbol: &str, conid: i64) -> Result<Vec<String>, IbkrError> {
+        let today = Utc::now().date_naive();
+        
+        let cached = self.conid_cache.lock().unwrap().get(symbol).cloned();
+        if let Some(contract) = &cached {
+            let fresh = contract.conid == conid && contract.months_as_of == Some(today);
+            if fresh && !contract.option_months.is_empty() {
+                return Ok(contract.option_months.clone());
+            }
+        }
+        
+        let json = self.search_contracts(symbol).await?;
+        let months = parse_option_months(&json, conid);
+        
+        if let Some(mut contract) = cached.filter(|contract| contract.conid == conid) {
+            contract.option_months = months.clone();
+            contract.months_as_of = Some(today);
+            self.conid_cache.lock().unwrap().insert(contract);
+        }
+        
+        Ok(months)
+    }
+    
+    pub async fn get_strikes(&self, conid: i64, month: &str) -> Result<(Vec<f64>, Vec<f64>), IbkrError> {
+        let conid = conid.to_string();
         
         let params = [
-            ("conid", "265598"), // Example contract ID for AAPL
+            ("conid", conid.as_str()),
             ("sectype", "OPT"),
-            ("month", "ALL"),
+            ("month", month),
             ("exchange", "SMART"),
-            ("strikeCount", &strike_count.to_string()),
         ];
         
-        let response = self.client.get(&url)
-            .query(&params)
-            .send()
-            .await
-            .map_err(IbkrError::RequestError)?;
+        let json = self.get_json("/v1/api/iserver/secdef/strikes", &params).await?;
+        Ok(parse_strikes(&json))
+    }
+    
+    // One secdef/info call for the whole month and right, keeping the wanted strikes. A gateway
+    // that insists on a strike rejects or empties the batched call, so fall back to one per strike.
+    async fn get_month_contracts(
+        &self,
+        symbol: &str,
+        conid: i64,
+        month: &str,
+        right: &str,
+        strikes: &[f64],
+    ) -> Result<Vec<OptionChain>, IbkrError> {
+        let wanted = |contract: &OptionChain| {
+            strikes.iter().any(|strike| (strike - contract.strike).abs() < 1e-6)
+        };
         
-        self.handle_response(response).await
+        let batched = match self.get_option_contracts(symbol, conid, month, None, right).await {
+            Ok(contracts) => contracts,
+            Err(e) if !e.is_transient() => {
+                debug!("Batched secdef/info for {} {} {} failed: {}", symbol, month, right, e);
+                Vec::new()
+            }
+            Err(e) => return Err(e),
+        };
+        
+        let contracts: Vec<OptionChain> = batched.into_iter().filter(|c| wanted(c)).collect();
+        if !contracts.is_empty() {
+            return Ok(contracts);
+        }
+        
+        let mut contracts = Vec::new();
+        
+        for strike in strikes {
+            match self.get_option_contracts(symbol, conid, month, Some(*strike), right).await {
+                Ok(mut found) => contracts.append(&mut found),
+                Err(e) => warn!(
+                    "Failed to resolve {} {} {} {}: {}",
+                    symbol, month, strike, right, e
+                ),
+            }
+        }
+        
+        Ok(contracts)
     }
     
-    pub async fn get_stock_price(&self, symbol: &str) -> Result<f64, IbkrError> {
-        info!("Fetching stock price for {}", symbol);
+    // Contracts for one month and right, narrowed to a single strike when given
+    pub async fn get_option_contracts(
+        &self,
+        symbol: &str,
+        conid: i64,
+        month: &str,
+        strike: Option<f64>,
+        right: &str,
+    ) -> Result<Vec<OptionChain>, IbkrError> {
+        let conid = conid.to_string();
+        let strike = strike.map(|strike| strike.to_string());
         
-        // This would call the actual IBKR market data API
-        let url = format!("{}/v1/api/iserver/marketdata/snapshot", self.base_url);
+        let mut params = vec![
+            ("conid", conid.as_str()),
+            ("sectype", "OPT"),
+            ("month", month),
+            ("exchange", "SMART"),
+            ("right", right),
+        ];
+        if let Some(strike) = &strike {
+            params.push(("strike", strike.as_str()));
+        }
         
+        let json = self.get_json("/v1/api/iserver/secdef/info", &params).await?;
+        Ok(parse_option_contracts(symbol, &json))
+    }
+    
+    async fn search_contracts(&self, symbol: &str) -> Result<Value, IbkrError> {
         let params = [
-            ("conids", "265598"), // Example contract ID for AAPL
-            ("fields", "31"), // Field 31 is last price
+            ("symbol", symbol),
+            ("secType", "STK"),
         ];
         
-        let response = self.client.get(&url)
-            .query(&params)
-            .send()
-            .await
-            .map_err(IbkrError::RequestError)?;
+        self.get_json("/v1/api/iserver/secdef/search", &params).await
+    }
+    
+    pub async fn get_stock_price(&self, symbol: &str) -> Result<f64, IbkrError> {
+        Ok(self.get_underlying_quote(symbol).await?.price)
+    }
+    
+    pub async fn get_underlying_quote(&self, symbol: &str) -> Result<UnderlyingQuote, IbkrError> {
+        info!("Fetching stock price for {}", symbol);
+        
+        let conid = self.resolve_conid(symbol).await?;
+        let snapshots = self.get_snapshots(&[conid], &UNDERLYING_FIELDS).await?;
+        let snapshot = snapshots.get(&conid);
+        
+        let price = snapshot
+            .and_then(|snapshot| snapshot.last.or_else(|| snapshot.price()))
+            .ok_or_else(|| IbkrError::DataError(format!("Price not found for {}", symbol)))?;
+        
+        Ok(UnderlyingQuote {
+            symbol: symbol.to_string(),
+            conid,
+            price,
+            dividend_amount: snapshot.and_then(|snapshot| snapshot.dividend_amount),
+            ex_dividend_date: snapshot.and_then(|snapshot| snapshot.ex_dividend_date),
+        })
+    }
+    
+    // Fill bid/ask/last/mark, volume, open interest and IV for each contract in the chain
+    pub async fn populate_quotes(&self, chains: &mut [OptionChain]) -> Result<(), IbkrError> {
+        let conids: Vec<i64> = chains
+            .iter()
+            .filter_map(|chain| chain.contract_id.parse().ok())
+            .collect();
+        
+        let snapshots = self.get_snapshots(&conids, &OPTION_FIELDS).await?;
         
-        let json: Value = response.json().await?;
+        for chain in chains.iter_mut() {
+            let Some(snapshot) = chain
+                .contract_id
+                .parse()
+                .ok()
+                .and_then(|conid: i64| snapshots.get(&conid))
+            else {
+                continue;
+            };
+            
+            chain.bid = snapshot.bid.unwrap_or(0.0);
+            chain.ask = snapshot.ask.unwrap_or(0.0);
+            chain.last = snapshot.last.unwrap_or(0.0);
+            chain.mark = snapshot.price().unwrap_or(0.0);
+            chain.option_price = chain.mark;
+            chain.volume = snapshot.volume.unwrap_or(0.0) as i32;
+            chain.open_interest = snapshot.open_interest.unwrap_or(0.0) as i32;
+            chain.implied_volatility = snapshot.implied_volatility.unwrap_or(0.0);
+        }
         
-        if let Some(price) = json[0]["31"].as_f64() {
-            Ok(price)
-        } else {
-            Err(IbkrError::DataError("Price not found in response".to_string()))
+        Ok(())
+    }
+    
+    // Batched /iserver/marketdata/snapshot, re-polling conids whose first response was empty
+    pub async fn get_snapshots(
+        &self,
+        conids: &[i64],
+        fields: &[&str],
+    ) -> Result<HashMap<i64, MarketSnapshot>, IbkrError> {
+        let fields = fields.join(",");
+        let mut snapshots = HashMap::new();
+        
+        for batch in conids.chunks(SNAPSHOT_BATCH_SIZE) {
+            let mut pending: Vec<i64> = batch.to_vec();
+            
+            for attempt in 1..=SNAPSHOT_POLL_ATTEMPTS {
+                let conid_list = pending
+                    .iter()
+                    .map(|conid| conid.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                
+                let params = [
+                    ("conids", conid_list.as_str()),
+                    ("fields", fields.as_str()),
+                ];
+                
+                let json = self.get_json("/v1/api/iserver/marketdata/snapshot", &params).await?;
+                
+                for (conid, snapshot) in parse_snapshots(&json) {
+                    if snapshot.has_quote() {
+                        pending.retain(|pending_conid| *pending_conid != conid);
+                        snapshots.insert(conid, snapshot);
+                    }
+                }
+                
+                if pending.is_empty() {
+                    break;
+                }
+                
+                if attempt < SNAPSHOT_POLL_ATTEMPTS {
+                    sleep(SNAPSHOT_POLL_DELAY).await;
+                }
+            }
+            
+            if !pending.is_empty() {
+                warn!("No market data for {} of {} contracts", pending.len(), batch.len());
+            }
         }
+        
+        Ok(snapshots)
     }
     
     pub async fn test_connection(&self) -> Result<bool, IbkrError> {
         info!("Testing connection to IBKR Gateway");
         
         let url = format!("{}/v1/api/tickle", self.base_url);
+        self.rate_limiter.acquire("/v1/api/tickle").await;
         
         match self.client.get(&url).send().await {
             Ok(response) => {
@@ -115,66 +429,323 @@ impl IbkrClient {
         }
     }
     
-    async fn handle_response(&self, response: Response) -> Result<Vec<OptionChain>, IbkrError> {
-        if !response.status().is_success() {
-            return Err(IbkrError::ApiError(format!(
-                "API request failed with status: {}",
-                response.status()
-            )));
-        }
+    pub async fn tickle(&self) -> Result<(), IbkrError> {
+        self.post_json("/v1/api/tickle").await.map(|_| ())
+    }
+    
+    pub async fn auth_status(&self) -> Result<AuthStatus, IbkrError> {
+        let json = self.post_json("/v1/api/iserver/auth/status").await?;
+        Ok(AuthStatus::from_json(&json))
+    }
+    
+    pub async fn reauthenticate(&self) -> Result<(), IbkrError> {
+        self.post_json("/v1/api/iserver/reauthenticate").await.map(|_| ())
+    }
+    
+    // Session endpoints are POST-only and are not retried; the session monitor polls them anyway
+    async fn post_json(&self, path: &str) -> Result<Value, IbkrError> {
+        let url = format!("{}{}", self.base_url, path);
+        self.rate_limiter.acquire(path).await;
         
-        let json: Value = response.json().await?;
+        let response = self.client.post(&url)
+            .header(reqwest::header::CONTENT_LENGTH, 0)
+            .send()
+            .await
+            .map_err(IbkrError::RequestError)?;
         
-        // Parse the response into OptionChain objects
-        // This is a simplified parsing - actual IBKR response would be more complex
-        self.parse_option_chain_response(json)
+        self.handle_response(response).await
     }
     
-    fn parse_option_chain_response(&self, json: Value) -> Result<Vec<OptionChain>, IbkrError> {
-        // This is a mock implementation
-        // In reality, you would parse the actual IBKR response format
-        let mut chains = Vec::new();
+    // GET with retries on transient failures; every gateway read goes through here
+    async fn get_json(&self, path: &str, params: &[(&str, &str)]) -> Result<Value, IbkrError> {
+        let url = format!("{}{}", self.base_url, path);
+        let max_attempts = self.retry_policy.max_attempts();
+        let mut attempt = 1;
         
-        // For demonstration, create mock data
-        // Real implementation would extract from JSON
-        if let Some(calls) = json.get("calls") {
-            if let Some(call_array) = calls.as_array() {
-                for call in call_array {
-                    if let Some(chain) = self.parse_option_data(call, OptionType::Call) {
-                        chains.push(chain);
+        loop {
+            self.rate_limiter.acquire(path).await;
+            
+            let error = match self.try_get_json(&url, params).await {
+                Ok(json) => return Ok(json),
+                Err(e) => e,
+            };
+            
+            // A permanent failure is reported as-is, even after earlier transient ones
+            if !error.is_transient() {
+                return Err(error);
+            }
+            
+            if attempt >= max_attempts {
+                return Err(if attempt > 1 {
+                    IbkrError::RetriesExhausted {
+                        attempts: attempt,
+                        source: Box::new(error),
                     }
-                }
+                } else {
+                    error
+                });
             }
+            
+            let delay = match &error {
+                IbkrError::HttpStatus { retry_after: Some(retry_after), .. } => {
+                    (*retry_after).min(self.retry_policy.max_delay)
+                }
+                _ => self.retry_policy.delay_for(attempt),
+            };
+            
+            warn!(
+                "Request to {} failed (attempt {}/{}): {}. Retrying in {:?}",
+                path, attempt, max_attempts, error, delay
+            );
+            
+            sleep(delay).await;
+            attempt += 1;
         }
+    }
+    
+    async fn try_get_json(&self, url: &str, params: &[(&str, &str)]) -> Result<Value, IbkrError> {
+        let response = self.client.get(url)
+            .query(params)
+            .send()
+            .await
+            .map_err(IbkrError::RequestError)?;
         
-        if let Some(puts) = json.get("puts") {
-            if let Some(put_array) = puts.as_array() {
-                for put in put_array {
-                    if let Some(chain) = self.parse_option_data(put, OptionType::Put) {
-                        chains.push(chain);
-                    }
-                }
-            }
+        self.handle_response(response).await
+    }
+    
+    async fn handle_response(&self, response: Response) -> Result<Value, IbkrError> {
+        let status = response.status();
+        
+        if !status.is_success() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs);
+            
+            return Err(IbkrError::HttpStatus {
+                status: status.as_u16(),
+                retry_after,
+            });
         }
         
-        Ok(chains)
+        Ok(response.json().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn test_client(server: &mockito::Server) -> IbkrClient {
+        IbkrClient::new(server.url(), String::new(), 5).with_retry_policy(RetryPolicy::new(
+            2,
+            Duration::from_millis(1),
+            Duration::from_millis(5),
+        ))
     }
     
-    fn parse_option_data(&self, data: &Value, option_type: OptionType) -> Option<OptionChain> {
-        // Parse individual option data
-        // This is a simplified version
-        Some(OptionChain {
-            symbol: data.get("symbol")?.as_str()?.to_string(),
-            option_type,
-            expiration: chrono::Utc::now(), // Would parse actual date
-            strike: data.get("strike")?.as_f64()?,
-            option_price: data.get("bid")?.as_f64()?,
-            stock_price: 0.0, // Will be populated separately
-            profit_percent: 0.0, // Will be calculated
-            volume: data.get("volume")?.as_i64()? as i32,
-            implied_volatility: data.get("iv")?.as_f64()?,
-            timestamp: chrono::Utc::now(),
-            contract_id: data.get("conid")?.as_str()?.to_string(),
-        })
+    async fn mock_search(server: &mut mockito::Server) -> mockito::Mock {
+        server.mock("GET", "/v1/api/iserver/secdef/search")
+            .match_query(mockito::Matcher::Any)
+            .with_body(r#"[{
+                "conid": "265598",
+                "symbol": "AAPL",
+                "description": "NASDAQ",
+                "sections": [{"secType": "STK"}, {"secType": "OPT", "months": "JAN25;FEB25"}]
+            }]"#)
+            .expect(1)
+            .create_async()
+            .await
+    }
+    
+    async fn mock_strikes(server: &mut mockito::Server) -> mockito::Mock {
+        server.mock("GET", "/v1/api/iserver/secdef/strikes")
+            .match_query(mockito::Matcher::Any)
+            .with_body(r#"{"call": [95.0, 100.0, 105.0], "put": [95.0, 100.0, 105.0]}"#)
+            .create_async()
+            .await
+    }
+    
+    fn info_entry(conid: i64, strike: f64) -> String {
+        format!(
+            r#"{{"conid": {}, "right": "C", "strike": {}, "maturityDate": "20250117"}}"#,
+            conid, strike
+        )
+    }
+    
+    #[tokio::test]
+    async fn test_chain_reuses_search_months_and_batches_info() {
+        let mut server = mockito::Server::new_async().await;
+        let search = mock_search(&mut server).await;
+        mock_strikes(&mut server).await;
+        let info = server.mock("GET", "/v1/api/iserver/secdef/info")
+            .match_query(mockito::Matcher::Any)
+            .with_body(format!(
+                "[{}, {}, {}]",
+                info_entry(1, 95.0),
+                info_entry(2, 100.0),
+                info_entry(3, 105.0)
+            ))
+            .expect(2)
+            .create_async()
+            .await;
+        let client = test_client(&server);
+        
+        for _ in 0..2 {
+            let chain = client
+                .get_option_chain("AAPL", 101.0, 2, 1, &[OptionType::Call])
+                .await
+                .unwrap();
+            let ids: Vec<&str> = chain.iter().map(|c| c.contract_id.as_str()).collect();
+            assert_eq!(ids, vec!["2", "3"]);
+        }
+        
+        // One search resolves the conid and lists the months; one info call per month and right
+        search.assert_async().await;
+        info.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_chain_falls_back_to_per_strike_info() {
+        let mut server = mockito::Server::new_async().await;
+        mock_search(&mut server).await;
+        mock_strikes(&mut server).await;
+        let batched = server.mock("GET", "/v1/api/iserver/secdef/info")
+            .match_query(mockito::Matcher::Any)
+            .with_status(400)
+            .expect(1)
+            .create_async()
+            .await;
+        let mut per_strike = Vec::new();
+        for (conid, strike) in [(2, "100"), (3, "105")] {
+            per_strike.push(
+                server.mock("GET", "/v1/api/iserver/secdef/info")
+                    .match_query(mockito::Matcher::UrlEncoded("strike".into(), strike.into()))
+                    .with_body(format!("[{}]", info_entry(conid, strike.parse().unwrap())))
+                    .expect(1)
+                    .create_async()
+                    .await,
+            );
+        }
+        
+        let chain = test_client(&server)
+            .get_option_chain("AAPL", 101.0, 2, 1, &[OptionType::Call])
+            .await
+            .unwrap();
+        
+        assert_eq!(chain.len(), 2);
+        batched.assert_async().await;
+        for mock in per_strike {
+            mock.assert_async().await;
+        }
+    }
+    
+    #[tokio::test]
+    async fn test_retries_server_errors_until_success() {
+        let mut server = mockito::Server::new_async().await;
+        let failing = server.mock("GET", "/v1/api/iserver/secdef/strikes")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+        let succeeding = server.mock("GET", "/v1/api/iserver/secdef/strikes")
+            .match_query(mockito::Matcher::Any)
+            .with_body(r#"{"call": [100.0], "put": [95.0]}"#)
+            .create_async()
+            .await;
+        
+        let (calls, puts) = test_client(&server).get_strikes(265598, "JAN25").await.unwrap();
+        
+        assert_eq!(calls, vec![100.0]);
+        assert_eq!(puts, vec![95.0]);
+        failing.assert_async().await;
+        succeeding.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_reports_attempt_count_when_exhausted() {
+        let mut server = mockito::Server::new_async().await;
+        server.mock("GET", "/v1/api/iserver/secdef/strikes")
+            .match_query(mockito::Matcher::Any)
+            .with_status(500)
+            .expect(3)
+            .create_async()
+            .await;
+        
+        let error = test_client(&server).get_strikes(265598, "JAN25").await.unwrap_err();
+        
+        assert!(matches!(error, IbkrError::RetriesExhausted { attempts: 3, .. }));
+    }
+    
+    #[tokio::test]
+    async fn test_retries_connections_dropped_after_connecting() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use tokio::io::AsyncReadExt;
+        
+        // Accepts the request, then closes the socket without a response
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let accepted = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&accepted);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut buffer = [0u8; 1024];
+                let _ = socket.read(&mut buffer).await;
+            }
+        });
+        let client = IbkrClient::new(format!("http://{}", address), String::new(), 5)
+            .with_retry_policy(RetryPolicy::new(
+                2,
+                Duration::from_millis(1),
+                Duration::from_millis(5),
+            ));
+        
+        let error = client.get_strikes(265598, "JAN25").await.unwrap_err();
+        
+        assert!(matches!(error, IbkrError::RetriesExhausted { attempts: 3, .. }));
+        assert_eq!(accepted.load(Ordering::SeqCst), 3);
+    }
+    
+    #[tokio::test]
+    async fn test_does_not_retry_client_errors() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/v1/api/iserver/secdef/strikes")
+            .match_query(mockito::Matcher::Any)
+            .with_status(400)
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let error = test_client(&server).get_strikes(265598, "JAN25").await.unwrap_err();
+        
+        assert!(matches!(error, IbkrError::HttpStatus { status: 400, .. }));
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_permanent_error_after_retry_is_not_wrapped() {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = server.mock("GET", "/v1/api/iserver/secdef/strikes")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let missing = server.mock("GET", "/v1/api/iserver/secdef/strikes")
+            .match_query(mockito::Matcher::Any)
+            .with_status(404)
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let error = test_client(&server).get_strikes(265598, "JAN25").await.unwrap_err();
+        
+        assert!(matches!(error, IbkrError::HttpStatus { status: 404, .. }));
+        unavailable.assert_async().await;
+        missing.assert_async().await;
     }
 }
@@ -0,0 +1,172 @@
+use crate::api::contract_resolver::parse_conid;
+use chrono::NaiveDate;
+use serde_json::Value;
+use std::collections::HashMap;
+
+// Snapshot field codes from the IBKR Client Portal market data reference
+pub const FIELD_LAST: &str = "31";
+pub const FIELD_BID: &str = "84";
+pub const FIELD_ASK: &str = "86";
+pub const FIELD_DIVIDEND_AMOUNT: &str = "7286";
+pub const FIELD_EX_DIVIDEND_DATE: &str = "7288";
+pub const FIELD_IMPLIED_VOL: &str = "7633";
+pub const FIELD_MARK: &str = "7635";
+pub const FIELD_OPEN_INTEREST: &str = "7638";
+pub const FIELD_VOLUME: &str = "7762";
+
+pub const UNDERLYING_FIELDS: [&str; 5] = [
+    FIELD_LAST,
+    FIELD_BID,
+    FIELD_ASK,
+    FIELD_DIVIDEND_AMOUNT,
+    FIELD_EX_DIVIDEND_DATE,
+];
+
+pub const OPTION_FIELDS: [&str; 7] = [
+    FIELD_LAST,
+    FIELD_BID,
+    FIELD_ASK,
+    FIELD_MARK,
+    FIELD_VOLUME,
+    FIELD_OPEN_INTEREST,
+    FIELD_IMPLIED_VOL,
+];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketSnapshot {
+    pub conid: i64,
+    pub last: Option<f64>,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    pub mark: Option<f64>,
+    pub volume: Option<f64>,
+    pub open_interest: Option<f64>,
+    pub implied_volatility: Option<f64>,
+    pub dividend_amount: Option<f64>,
+    pub ex_dividend_date: Option<NaiveDate>,
+}
+
+// Stock price plus the next announced dividend, if any
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnderlyingQuote {
+    pub symbol: String,
+    pub conid: i64,
+    pub price: f64,
+    pub dividend_amount: Option<f64>,
+    pub ex_dividend_date: Option<NaiveDate>,
+}
+
+impl MarketSnapshot {
+    // The first request for a conid only opens the subscription and comes back without prices
+    pub fn has_quote(&self) -> bool {
+        self.last.is_some() || self.bid.is_some() || self.ask.is_some() || self.mark.is_some()
+    }
+
+    // Mark if the gateway provides one, otherwise the bid/ask midpoint, otherwise last
+    pub fn price(&self) -> Option<f64> {
+        self.mark
+            .or_else(|| match (self.bid, self.ask) {
+                (Some(bid), Some(ask)) if bid > 0.0 && ask > 0.0 => Some((bid + ask) / 2.0),
+                _ => None,
+            })
+            .or(self.last)
+    }
+}
+
+pub fn parse_snapshots(json: &Value) -> HashMap<i64, MarketSnapshot> {
+    json.as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(parse_snapshot)
+                .map(|snapshot| (snapshot.conid, snapshot))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_snapshot(entry: &Value) -> Option<MarketSnapshot> {
+    let field = |code: &str| entry.get(code).and_then(parse_field_value);
+
+    Some(MarketSnapshot {
+        conid: parse_conid(entry.get("conid")?)?,
+        last: field(FIELD_LAST),
+        bid: field(FIELD_BID),
+        ask: field(FIELD_ASK),
+        mark: field(FIELD_MARK),
+        volume: field(FIELD_VOLUME),
+        open_interest: field(FIELD_OPEN_INTEREST),
+        implied_volatility: field(FIELD_IMPLIED_VOL).map(|iv| iv / 100.0),
+        dividend_amount: field(FIELD_DIVIDEND_AMOUNT).filter(|amount| *amount > 0.0),
+        ex_dividend_date: entry
+            .get(FIELD_EX_DIVIDEND_DATE)
+            .and_then(Value::as_str)
+            .and_then(parse_date),
+    })
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    ["%Y%m%d", "%Y-%m-%d", "%m/%d/%Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value.trim(), format).ok())
+}
+
+// Snapshot values arrive as display strings: "C185.50" (prior close), "1.2K", "25.3%", "1,024"
+pub fn parse_field_value(value: &Value) -> Option<f64> {
+    let text = match value {
+        Value::Number(n) => return n.as_f64(),
+        Value::String(s) => s.trim(),
+        _ => return None,
+    };
+
+    let text = text.trim_start_matches(['C', 'H']).trim_end_matches('%').replace(',', "");
+
+    let (number, scale) = match text.chars().last()? {
+        'K' => (&text[..text.len() - 1], 1_000.0),
+        'M' => (&text[..text.len() - 1], 1_000_000.0),
+        'B' => (&text[..text.len() - 1], 1_000_000_000.0),
+        _ => (text.as_str(), 1.0),
+    };
+
+    number.parse::<f64>().ok().map(|n| n * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_field_value() {
+        assert_eq!(parse_field_value(&json!("185.50")), Some(185.5));
+        assert_eq!(parse_field_value(&json!("C185.50")), Some(185.5));
+        assert_eq!(parse_field_value(&json!("1.2K")), Some(1200.0));
+        assert_eq!(parse_field_value(&json!("1,024")), Some(1024.0));
+        assert_eq!(parse_field_value(&json!("25.3%")), Some(25.3));
+        assert_eq!(parse_field_value(&json!(42)), Some(42.0));
+        assert_eq!(parse_field_value(&json!("")), None);
+    }
+
+    #[test]
+    fn test_parse_snapshots() {
+        let json = json!([
+            {"conid": 733405201, "31": "2.45", "84": "2.40", "86": "2.50", "7638": "1.5K", "7633": "28.4%"},
+            {"conid": 733405202, "_updated": 0},
+            {"conid": 265598, "31": "185.20", "7286": "0.25", "7288": "20250207"}
+        ]);
+
+        let snapshots = parse_snapshots(&json);
+
+        let quoted = &snapshots[&733405201];
+        assert!(quoted.has_quote());
+        assert_eq!(quoted.open_interest, Some(1500.0));
+        assert!((quoted.price().unwrap() - 2.45).abs() < 1e-9);
+        assert!((quoted.implied_volatility.unwrap() - 0.284).abs() < 1e-9);
+
+        assert!(!snapshots[&733405202].has_quote());
+
+        let underlying = &snapshots[&265598];
+        assert_eq!(underlying.dividend_amount, Some(0.25));
+        assert_eq!(underlying.ex_dividend_date, NaiveDate::from_ymd_opt(2025, 2, 7));
+    }
+}
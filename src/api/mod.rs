@@ -1,3 +1,14 @@
+pub mod contract_resolver;
 pub mod ibkr_client;
+pub mod market_data;
+pub mod rate_limiter;
+pub mod retry;
+pub mod secdef;
+pub mod session;
 
+pub use contract_resolver::*;
 pub use ibkr_client::*;
+pub use market_data::{MarketSnapshot, UnderlyingQuote};
+pub use rate_limiter::RateLimiter;
+pub use retry::RetryPolicy;
+pub use session::{AuthStatus, SessionMonitor, SessionState};
@@ -0,0 +1,157 @@
+use crate::models::EndpointRateLimit;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+// IBKR Client Portal pacing: ~10 req/s per session, tighter on market data and secdef
+pub const DEFAULT_GLOBAL_REQUESTS_PER_SECOND: f64 = 10.0;
+
+const API_PREFIX: &str = "/v1/api";
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_second: f64,
+    state: Mutex<BucketState>,
+}
+
+impl TokenBucket {
+    // Burst capacity equals one second worth of requests. Rates come from validated config
+    // (see positive_rate), so a non-positive one is a programming error.
+    pub fn new(requests_per_second: f64) -> Self {
+        assert!(
+            requests_per_second > 0.0,
+            "token bucket rate must be positive, got {}",
+            requests_per_second
+        );
+        let capacity = requests_per_second.max(1.0);
+
+        Self {
+            capacity,
+            refill_per_second: requests_per_second,
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        while let Some(wait) = self.try_acquire() {
+            sleep(wait).await;
+        }
+    }
+
+    // Takes a token, or returns how long until one is available
+    fn try_acquire(&self) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap();
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        state.last_refill = now;
+
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                (1.0 - state.tokens) / self.refill_per_second,
+            ))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    global: TokenBucket,
+    endpoints: Vec<(String, TokenBucket)>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_GLOBAL_REQUESTS_PER_SECOND, &EndpointRateLimit::defaults())
+    }
+}
+
+impl RateLimiter {
+    pub fn new(global_requests_per_second: f64, endpoints: &[EndpointRateLimit]) -> Self {
+        let mut endpoints: Vec<(String, TokenBucket)> = endpoints
+            .iter()
+            .map(|limit| {
+                (
+                    limit.path_prefix.clone(),
+                    TokenBucket::new(limit.requests_per_second),
+                )
+            })
+            .collect();
+
+        // Most specific prefix wins
+        endpoints.sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+
+        Self {
+            global: TokenBucket::new(global_requests_per_second),
+            endpoints,
+        }
+    }
+
+    // Waits for the endpoint bucket (if any) and then the session-wide bucket
+    pub async fn acquire(&self, path: &str) {
+        let path = path.strip_prefix(API_PREFIX).unwrap_or(path);
+
+        if let Some((_, bucket)) = self
+            .endpoints
+            .iter()
+            .find(|(prefix, _)| path.starts_with(prefix.as_str()))
+        {
+            bucket.acquire().await;
+        }
+
+        self.global.acquire().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_burst_then_waits() {
+        let bucket = TokenBucket::new(2.0);
+
+        assert!(bucket.try_acquire().is_none());
+        assert!(bucket.try_acquire().is_none());
+
+        let wait = bucket.try_acquire().expect("bucket should be empty");
+        assert!(wait > Duration::from_millis(400) && wait <= Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_endpoint_bucket_paces_requests() {
+        let limiter = RateLimiter::new(
+            1000.0,
+            &[EndpointRateLimit {
+                path_prefix: "/iserver/marketdata/snapshot".to_string(),
+                requests_per_second: 20.0,
+            }],
+        );
+
+        let start = Instant::now();
+        for _ in 0..25 {
+            limiter.acquire("/v1/api/iserver/marketdata/snapshot").await;
+        }
+
+        // 20 burst tokens, then 5 more at 20/s
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        let start = Instant::now();
+        limiter.acquire("/v1/api/iserver/secdef/search").await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+}
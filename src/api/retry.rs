@@ -0,0 +1,103 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+// Capped exponential backoff with jitter for idempotent gateway requests
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            base_delay,
+            max_delay,
+        }
+    }
+
+    // MAX_RETRY_ATTEMPTS counts the first try; fewer than one still makes a single attempt
+    pub fn from_attempts(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self::new(max_attempts.saturating_sub(1), base_delay, max_delay)
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_retries + 1
+    }
+
+    // Upper bound of the wait after the given (1-based) failed attempt
+    pub fn backoff_ceiling(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        self.base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay)
+    }
+
+    // Equal jitter: half the ceiling plus a random share of the other half,
+    // so concurrent scans that failed together don't retry in lockstep
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let ceiling = self.backoff_ceiling(attempt);
+        let half = ceiling / 2;
+        half + half.mul_f64(random_fraction())
+    }
+}
+
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_millis(500));
+
+        assert_eq!(policy.max_attempts(), 6);
+        assert_eq!(policy.backoff_ceiling(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff_ceiling(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff_ceiling(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff_ceiling(4), Duration::from_millis(500));
+        assert_eq!(policy.backoff_ceiling(40), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_from_attempts_counts_the_first_try() {
+        let delay = Duration::from_millis(100);
+
+        assert_eq!(RetryPolicy::from_attempts(3, delay, delay).max_attempts(), 3);
+        assert_eq!(RetryPolicy::from_attempts(0, delay, delay).max_attempts(), 1);
+    }
+
+    #[test]
+    fn test_delay_stays_within_jitter_range() {
+        let policy = RetryPolicy::default();
+
+        for attempt in 1..=6 {
+            let ceiling = policy.backoff_ceiling(attempt);
+            let delay = policy.delay_for(attempt);
+            assert!(delay >= ceiling / 2 && delay <= ceiling);
+        }
+    }
+}
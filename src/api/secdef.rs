@@ -0,0 +1,202 @@
+use crate::api::contract_resolver::parse_conid;
+use crate::models::{IvStatus, OptionChain, OptionMetrics, OptionType};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::Value;
+
+// US equity options stop trading at 16:00 New York, roughly 20:00 UTC
+const EXPIRY_CLOSE_HOUR_UTC: u32 = 20;
+
+// Option months ("JAN25;FEB25") listed in the OPT section of a secdef/search entry
+pub fn parse_option_months(json: &Value, conid: i64) -> Vec<String> {
+    let entry = json
+        .as_array()
+        .and_then(|results| {
+            results
+                .iter()
+                .find(|entry| entry.get("conid").and_then(parse_conid) == Some(conid))
+        });
+
+    let months = entry
+        .and_then(|entry| entry.get("sections"))
+        .and_then(Value::as_array)
+        .and_then(|sections| {
+            sections
+                .iter()
+                .find(|s| s.get("secType").and_then(Value::as_str) == Some("OPT"))
+        })
+        .and_then(|section| section.get("months"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+
+    months
+        .split(';')
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect()
+}
+
+// Call and put strikes from a secdef/strikes response
+pub fn parse_strikes(json: &Value) -> (Vec<f64>, Vec<f64>) {
+    let strikes = |key: &str| -> Vec<f64> {
+        json.get(key)
+            .and_then(Value::as_array)
+            .map(|values| values.iter().filter_map(Value::as_f64).collect())
+            .unwrap_or_default()
+    };
+
+    (strikes("call"), strikes("put"))
+}
+
+// Keep the `count` strikes closest to the underlying price, in ascending order
+pub fn strikes_near(strikes: &[f64], underlying_price: f64, count: usize) -> Vec<f64> {
+    let mut selected = strikes.to_vec();
+
+    if underlying_price > 0.0 {
+        selected.sort_by(|a, b| {
+            (a - underlying_price)
+                .abs()
+                .total_cmp(&(b - underlying_price).abs())
+        });
+        selected.truncate(count);
+    } else if selected.len() > count {
+        // No reference price, fall back to the middle of the listed range
+        let start = (selected.len() - count) / 2;
+        selected = selected[start..start + count].to_vec();
+    }
+
+    selected.sort_by(f64::total_cmp);
+    selected
+}
+
+// Option contracts returned by secdef/info for a single strike and right
+pub fn parse_option_contracts(symbol: &str, json: &Value) -> Vec<OptionChain> {
+    json.as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| parse_option_contract(symbol, entry))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_option_contract(symbol: &str, entry: &Value) -> Option<OptionChain> {
+    let option_type = match entry.get("right")?.as_str()? {
+        "C" => OptionType::Call,
+        "P" => OptionType::Put,
+        _ => return None,
+    };
+
+    let strike = match entry.get("strike")? {
+        Value::String(s) => s.parse().ok()?,
+        value => value.as_f64()?,
+    };
+
+    let multiplier = entry
+        .get("multiplier")
+        .and_then(|m| match m {
+            Value::String(s) => s.parse().ok(),
+            value => value.as_f64(),
+        })
+        .unwrap_or(100.0);
+
+    Some(OptionChain {
+        symbol: symbol.to_string(),
+        option_type,
+        expiration: parse_maturity_date(entry.get("maturityDate")?.as_str()?)?,
+        strike,
+        multiplier,
+        option_price: 0.0, // Populated from market data
+        bid: 0.0,
+        ask: 0.0,
+        last: 0.0,
+        mark: 0.0,
+        stock_price: 0.0, // Will be populated separately
+        profit_percent: 0.0, // Will be calculated
+        trading_cost: 0.0,
+        volume: 0,
+        open_interest: 0,
+        implied_volatility: 0.0,
+        iv_status: IvStatus::Unknown,
+        theoretical_value: 0.0, // Greeks are computed by the scanner
+        early_exercise_premium: 0.0,
+        delta: 0.0,
+        gamma: 0.0,
+        theta: 0.0,
+        vega: 0.0,
+        rho: 0.0,
+        prob_itm: 0.0,
+        prob_profit: 0.0,
+        prob_touch: 0.0,
+        expected_value: 0.0,
+        score: None,
+        metrics: OptionMetrics::default(), // Filled in by the scanner
+        timestamp: Utc::now(),
+        contract_id: parse_conid(entry.get("conid")?)?.to_string(),
+    })
+}
+
+fn parse_maturity_date(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .ok()?
+        .and_hms_opt(EXPIRY_CLOSE_HOUR_UTC, 0, 0)
+        .map(|dt| dt.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_option_months() {
+        let json = json!([{
+            "conid": "265598",
+            "symbol": "AAPL",
+            "sections": [
+                {"secType": "STK"},
+                {"secType": "OPT", "months": "JAN25;FEB25;MAR25", "exchange": "SMART;AMEX"}
+            ]
+        }]);
+
+        assert_eq!(parse_option_months(&json, 265598), vec!["JAN25", "FEB25", "MAR25"]);
+        assert!(parse_option_months(&json, 1).is_empty());
+    }
+
+    #[test]
+    fn test_strikes_near() {
+        let strikes = [90.0, 95.0, 100.0, 105.0, 110.0, 115.0];
+
+        assert_eq!(strikes_near(&strikes, 103.0, 3), vec![100.0, 105.0, 110.0]);
+        assert_eq!(strikes_near(&strikes, 0.0, 2), vec![100.0, 105.0]);
+    }
+
+    #[test]
+    fn test_parse_option_contracts() {
+        let json = json!([
+            {
+                "conid": 733405201,
+                "symbol": "AAPL",
+                "right": "C",
+                "strike": 185.0,
+                "maturityDate": "20250117",
+                "multiplier": "100"
+            },
+            {
+                "conid": 733405202,
+                "symbol": "AAPL",
+                "right": "C",
+                "strike": 185.0,
+                "maturityDate": "20250124",
+                "multiplier": "100"
+            }
+        ]);
+
+        let contracts = parse_option_contracts("AAPL", &json);
+        assert_eq!(contracts.len(), 2);
+        assert_eq!(contracts[0].contract_id, "733405201");
+        assert_eq!(contracts[0].option_type, OptionType::Call);
+        assert_eq!(contracts[0].multiplier, 100.0);
+        assert_eq!(contracts[1].expiration.format("%Y-%m-%d").to_string(), "2025-01-24");
+    }
+}
@@ -0,0 +1,212 @@
+use crate::api::IbkrClient;
+use log::{error, info, warn};
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+// How often to re-check while the session is not usable
+const RECOVERY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuthStatus {
+    pub authenticated: bool,
+    pub connected: bool,
+    pub competing: bool,
+    pub message: String,
+}
+
+impl AuthStatus {
+    pub fn from_json(json: &Value) -> Self {
+        let flag = |key: &str| json.get(key).and_then(Value::as_bool).unwrap_or(false);
+
+        Self {
+            authenticated: flag("authenticated"),
+            connected: flag("connected"),
+            competing: flag("competing"),
+            message: json
+                .get("message")
+                .or_else(|| json.get("fail"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+
+    pub fn session_state(&self) -> SessionState {
+        if self.competing {
+            SessionState::Competing
+        } else if !self.connected {
+            SessionState::Disconnected
+        } else if !self.authenticated {
+            SessionState::Unauthenticated
+        } else {
+            SessionState::Ready
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionState {
+    Unknown,
+    Ready,
+    Unauthenticated,
+    Disconnected,
+    Competing,
+    Unreachable(String),
+}
+
+impl SessionState {
+    pub fn is_ready(&self) -> bool {
+        matches!(self, SessionState::Ready)
+    }
+
+    // Competing sessions need the user to log out elsewhere; reauthenticating would just fight it
+    fn should_reauthenticate(&self) -> bool {
+        matches!(self, SessionState::Unauthenticated | SessionState::Disconnected)
+    }
+}
+
+impl fmt::Display for SessionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionState::Unknown => write!(f, "not checked yet"),
+            SessionState::Ready => write!(f, "authenticated"),
+            SessionState::Unauthenticated => write!(f, "not authenticated"),
+            SessionState::Disconnected => write!(f, "disconnected from IBKR backend"),
+            SessionState::Competing => {
+                write!(f, "competing session open elsewhere (log out of TWS/mobile)")
+            }
+            SessionState::Unreachable(reason) => write!(f, "gateway unreachable: {}", reason),
+        }
+    }
+}
+
+// Background task that tickles the gateway and keeps the brokerage session alive
+pub struct SessionMonitor {
+    state: watch::Receiver<SessionState>,
+    task: JoinHandle<()>,
+}
+
+impl SessionMonitor {
+    pub fn spawn(client: Arc<IbkrClient>, tickle_interval: Duration) -> Self {
+        let (sender, state) = watch::channel(SessionState::Unknown);
+
+        let task = tokio::spawn(async move {
+            loop {
+                let current = check_session(&client).await;
+
+                sender.send_if_modified(|previous| {
+                    if *previous == current {
+                        return false;
+                    }
+
+                    if current.is_ready() {
+                        info!("Gateway session is {}", current);
+                    } else {
+                        warn!("Gateway session is {}", current);
+                    }
+
+                    *previous = current.clone();
+                    true
+                });
+
+                let wait = if current.is_ready() {
+                    tickle_interval
+                } else {
+                    tickle_interval.min(RECOVERY_CHECK_INTERVAL)
+                };
+
+                sleep(wait).await;
+            }
+        });
+
+        Self { state, task }
+    }
+
+    pub fn state(&self) -> SessionState {
+        self.state.borrow().clone()
+    }
+
+    // Blocks the caller until the session is usable again
+    pub async fn wait_until_ready(&mut self) {
+        let mut reported = false;
+
+        loop {
+            let state = self.state.borrow_and_update().clone();
+
+            if state.is_ready() {
+                if reported {
+                    println!("Gateway session restored, resuming scans.");
+                }
+                return;
+            }
+
+            if state != SessionState::Unknown {
+                println!("Scanning paused: gateway session is {}. Waiting...", state);
+                reported = true;
+            }
+
+            if self.state.changed().await.is_err() {
+                error!("Session monitor stopped unexpectedly");
+                return;
+            }
+        }
+    }
+}
+
+impl Drop for SessionMonitor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn check_session(client: &IbkrClient) -> SessionState {
+    if let Err(e) = client.tickle().await {
+        return SessionState::Unreachable(e.to_string());
+    }
+
+    let state = match client.auth_status().await {
+        Ok(status) => status.session_state(),
+        Err(e) => return SessionState::Unreachable(e.to_string()),
+    };
+
+    if state.should_reauthenticate() {
+        info!("Session is {}, requesting reauthentication", state);
+
+        if let Err(e) = client.reauthenticate().await {
+            warn!("Reauthentication request failed: {}", e);
+        }
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_auth_status_states() {
+        let ready = AuthStatus::from_json(&json!({
+            "authenticated": true, "connected": true, "competing": false
+        }));
+        assert_eq!(ready.session_state(), SessionState::Ready);
+
+        let expired = AuthStatus::from_json(&json!({
+            "authenticated": false, "connected": true, "competing": false
+        }));
+        assert_eq!(expired.session_state(), SessionState::Unauthenticated);
+        assert!(expired.session_state().should_reauthenticate());
+
+        let competing = AuthStatus::from_json(&json!({
+            "authenticated": true, "connected": true, "competing": true
+        }));
+        assert_eq!(competing.session_state(), SessionState::Competing);
+        assert!(!competing.session_state().should_reauthenticate());
+    }
+}
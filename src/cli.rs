@@ -0,0 +1,607 @@
+use clap::{Args, Parser, Subcommand};
+use ibkr_nasdaq_scanner::models::{
+    positive_rate, tickle_interval, ConfigError, EndpointRateLimit, FeePreset, FeeSchedule,
+    FilterExpression, ScannerConfig, SortExpression, SortKey,
+};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(name = "ibkr_nasdaq_scanner", version, about = "NASDAQ option chain scanner for the IBKR Web API")]
+pub struct Cli {
+    /// Load environment variables from this file instead of ./.env
+    #[arg(long, global = true, value_name = "PATH")]
+    pub env_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub overrides: ConfigOverrides,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Scan the watchlist (continuously unless --once is given)
+    Scan {
+        /// Run a single scan cycle and exit
+        #[arg(long)]
+        once: bool,
+    },
+    /// Scan continuously every scan interval (default)
+    Watch,
+    /// Check gateway reachability and brokerage session status
+    CheckConnection,
+    /// List available watchlists, or validate and print the selected one
+    Watchlists {
+        /// Print the symbols of the selected watchlist instead of listing names
+        #[arg(long)]
+        show: bool,
+    },
+    /// List the registered scoring strategies
+    Strategies,
+    /// Inspect the effective configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Print the configuration after env file and flag overrides
+    Show,
+}
+
+// Every flag overrides the matching ScannerConfig field loaded from the environment
+#[derive(Debug, Default, Args)]
+pub struct ConfigOverrides {
+    /// IBKR gateway host
+    #[arg(long, global = true)]
+    pub host: Option<String>,
+    /// IBKR gateway port
+    #[arg(long, global = true)]
+    pub port: Option<u16>,
+    /// Use https for the gateway
+    #[arg(long, global = true, value_name = "BOOL")]
+    pub ssl: Option<bool>,
+    /// Comma-separated symbols to scan (ignores any configured watchlist)
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub symbols: Option<Vec<String>>,
+    /// Named watchlist(s) to scan, e.g. "nasdaq_stocks,income", or a file path
+    #[arg(long, global = true, value_name = "NAMES")]
+    pub watchlist: Option<String>,
+    /// Directory holding <name>.txt / <name>.csv watchlists
+    #[arg(long, global = true, value_name = "DIR")]
+    pub watchlist_dir: Option<String>,
+    /// Minimum profit percent for an option to be reported
+    #[arg(long, global = true)]
+    pub min_profit: Option<f64>,
+    /// Minimum probability of expiring in the money, 0.0-1.0
+    #[arg(long, global = true)]
+    pub min_prob_itm: Option<f64>,
+    /// Minimum probability of touching breakeven before expiry, 0.0-1.0
+    #[arg(long, global = true)]
+    pub min_prob_touch: Option<f64>,
+    /// Minimum expected value per contract in dollars
+    #[arg(long, global = true, allow_hyphen_values = true)]
+    pub min_expected_value: Option<f64>,
+    /// Order the results table by profit, breakeven, max_gain, max_loss, return, annualized,
+    /// dte, prob_itm, prob_touch or ev
+    #[arg(long, global = true, value_name = "KEY")]
+    pub sort_by: Option<SortKey>,
+    /// Long options must satisfy this instead of the minimum profit, e.g.
+    /// "dte between 7 and 30 and spread_pct < 5 and annualized > 20"
+    #[arg(long, global = true, value_name = "EXPR")]
+    pub filter_expr: Option<FilterExpression>,
+    /// Order the results table by these keys instead of --sort-by, e.g.
+    /// "prob_profit desc, dte asc"
+    #[arg(long, global = true, value_name = "EXPR")]
+    pub sort_expr: Option<SortExpression>,
+    /// Comma-separated strategies to run, e.g. "long_options,covered_calls"
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub strategies: Option<Vec<String>>,
+    /// Skip contracts that traded fewer than this many times today
+    #[arg(long, global = true)]
+    pub min_volume: Option<i32>,
+    /// Skip contracts with less open interest than this
+    #[arg(long, global = true)]
+    pub min_open_interest: Option<i32>,
+    /// Skip contracts expiring in fewer days than this
+    #[arg(long, global = true)]
+    pub min_dte: Option<f64>,
+    /// Skip contracts expiring in more days than this
+    #[arg(long, global = true)]
+    pub max_dte: Option<f64>,
+    /// Minimum percent out of the money (negative allows in the money)
+    #[arg(long, global = true, allow_hyphen_values = true)]
+    pub min_moneyness: Option<f64>,
+    /// Maximum percent out of the money
+    #[arg(long, global = true, allow_hyphen_values = true)]
+    pub max_moneyness: Option<f64>,
+    /// Minimum absolute delta
+    #[arg(long, global = true)]
+    pub min_delta: Option<f64>,
+    /// Maximum absolute delta
+    #[arg(long, global = true)]
+    pub max_delta: Option<f64>,
+    /// Maximum bid/ask spread as a percent of the midpoint
+    #[arg(long, global = true)]
+    pub max_spread_percent: Option<f64>,
+    /// Minimum option premium per share
+    #[arg(long, global = true)]
+    pub min_premium: Option<f64>,
+    /// Commission preset: ibkr_fixed, ibkr_tiered or custom (resets the fee values below)
+    #[arg(long, global = true, value_name = "PRESET")]
+    pub fee_schedule: Option<FeePreset>,
+    /// Commission per option contract in dollars
+    #[arg(long, global = true)]
+    pub commission_per_contract: Option<f64>,
+    /// Minimum commission per order in dollars
+    #[arg(long, global = true)]
+    pub commission_min: Option<f64>,
+    /// Exchange fee per option contract in dollars
+    #[arg(long, global = true)]
+    pub exchange_fee: Option<f64>,
+    /// Regulatory and clearing fees per option contract in dollars
+    #[arg(long, global = true)]
+    pub regulatory_fee: Option<f64>,
+    /// Count the bid/ask spread as a trading cost
+    #[arg(long, global = true, value_name = "BOOL")]
+    pub include_spread_cost: Option<bool>,
+    /// Covered calls: minimum static return percent
+    #[arg(long, global = true)]
+    pub cc_min_static_return: Option<f64>,
+    /// Covered calls: minimum if-called return percent
+    #[arg(long, global = true)]
+    pub cc_min_if_called_return: Option<f64>,
+    /// Covered calls: minimum downside protection percent
+    #[arg(long, global = true)]
+    pub cc_min_downside_protection: Option<f64>,
+    /// Covered calls: minimum annualized yield percent
+    #[arg(long, global = true)]
+    pub cc_min_annualized_yield: Option<f64>,
+    /// Cash-secured puts: minimum premium yield on collateral percent
+    #[arg(long, global = true)]
+    pub csp_min_yield: Option<f64>,
+    /// Cash-secured puts: maximum absolute delta
+    #[arg(long, global = true)]
+    pub csp_max_delta: Option<f64>,
+    /// Vertical spreads: maximum strike width in dollars
+    #[arg(long, global = true)]
+    pub spread_max_width: Option<f64>,
+    /// Vertical spreads: minimum credit per share for credit spreads
+    #[arg(long, global = true)]
+    pub spread_min_credit: Option<f64>,
+    /// Vertical spreads: best spreads kept per symbol
+    #[arg(long, global = true)]
+    pub spread_top_per_symbol: Option<usize>,
+    /// Iron condors: distance in dollars from each short strike to its wing
+    #[arg(long, global = true)]
+    pub condor_wing_width: Option<f64>,
+    /// Neutral setups: minimum ratio against the expected move
+    #[arg(long, global = true)]
+    pub min_move_ratio: Option<f64>,
+    /// Calendars: minimum front minus back month IV in vol points
+    #[arg(long, global = true, allow_hyphen_values = true)]
+    pub calendar_min_iv_diff: Option<f64>,
+    /// Calendars: also pair adjacent strikes (diagonals)
+    #[arg(long, global = true, value_name = "BOOL")]
+    pub calendar_diagonals: Option<bool>,
+    /// Calendars: best spreads kept per symbol
+    #[arg(long, global = true)]
+    pub calendar_top_per_symbol: Option<usize>,
+    /// Rank long options on weighted return, probability, liquidity and IV rank, and strategy
+    /// candidates on their own scores
+    #[arg(long, global = true, value_name = "BOOL")]
+    pub ranking: Option<bool>,
+    /// Ranking: weight of the one sigma move return
+    #[arg(long, global = true, allow_hyphen_values = true)]
+    pub rank_return_weight: Option<f64>,
+    /// Ranking: weight of probability of profit
+    #[arg(long, global = true, allow_hyphen_values = true)]
+    pub rank_probability_weight: Option<f64>,
+    /// Ranking: weight of liquidity (volume, open interest, spread)
+    #[arg(long, global = true, allow_hyphen_values = true)]
+    pub rank_liquidity_weight: Option<f64>,
+    /// Ranking: weight of IV rank within the symbol (negative favours cheap volatility)
+    #[arg(long, global = true, allow_hyphen_values = true)]
+    pub rank_iv_weight: Option<f64>,
+    /// Ranking: keep only the best N options per cycle (0 keeps all)
+    #[arg(long, global = true)]
+    pub top_n: Option<usize>,
+    /// Ranking: keep only the best K options per symbol (0 keeps all)
+    #[arg(long, global = true)]
+    pub top_per_symbol: Option<usize>,
+    /// Report new, gone and changed options against the previous cycle
+    #[arg(long, global = true, value_name = "BOOL")]
+    pub cycle_diff: Option<bool>,
+    /// Diff: minimum price move in percent to count as changed
+    #[arg(long, global = true)]
+    pub diff_min_price_change: Option<f64>,
+    /// Diff: minimum profit move in percentage points
+    #[arg(long, global = true)]
+    pub diff_min_profit_change: Option<f64>,
+    /// Diff: minimum IV move in vol points
+    #[arg(long, global = true)]
+    pub diff_min_iv_change: Option<f64>,
+    /// Annual risk-free rate for option pricing, e.g. 0.045
+    #[arg(long, global = true)]
+    pub risk_free_rate: Option<f64>,
+    /// Annual continuous dividend yield for option pricing, e.g. 0.01
+    #[arg(long, global = true)]
+    pub dividend_yield: Option<f64>,
+    /// Binomial tree steps for American option pricing
+    #[arg(long, global = true)]
+    pub tree_steps: Option<usize>,
+    /// Seconds between scan cycles
+    #[arg(long, global = true)]
+    pub scan_interval: Option<u64>,
+    /// Seconds between session keep-alive tickles
+    #[arg(long, global = true)]
+    pub tickle_interval: Option<u64>,
+    /// Attempts per request, including the first, for transient failures
+    #[arg(long, global = true)]
+    pub max_retry_attempts: Option<u32>,
+    /// Initial retry backoff in milliseconds
+    #[arg(long, global = true)]
+    pub retry_base_delay_ms: Option<u64>,
+    /// Maximum retry backoff in milliseconds
+    #[arg(long, global = true)]
+    pub retry_max_delay_ms: Option<u64>,
+    /// HTTP request timeout in seconds
+    #[arg(long, global = true)]
+    pub timeout: Option<u64>,
+    /// Global gateway request budget per second
+    #[arg(long, global = true)]
+    pub rate_limit: Option<f64>,
+    /// Per-endpoint budgets, e.g. "/iserver/marketdata/snapshot=5,/iserver/secdef=5"
+    #[arg(long, global = true, value_name = "LIST")]
+    pub endpoint_rate_limits: Option<String>,
+    /// Symbols scanned in parallel
+    #[arg(long, global = true)]
+    pub max_concurrent: Option<usize>,
+    /// Strikes per expiry closest to the stock price
+    #[arg(long, global = true)]
+    pub strike_count: Option<usize>,
+    /// Option months to scan, nearest first
+    #[arg(long, global = true)]
+    pub option_months: Option<usize>,
+    /// IBKR account ID
+    #[arg(long, global = true)]
+    pub account_id: Option<String>,
+    /// Scanner log file path
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<String>,
+    /// Directory for option CSV and summary logs
+    #[arg(long, global = true, value_name = "DIR")]
+    pub options_log_dir: Option<String>,
+    /// Symbol to contract ID cache file
+    #[arg(long, global = true, value_name = "PATH")]
+    pub conid_cache: Option<String>,
+}
+
+impl ConfigOverrides {
+    pub fn apply(self, config: &mut ScannerConfig) -> Result<(), ConfigError> {
+        if let Some(host) = self.host {
+            config.ibkr_gateway_host = host;
+        }
+        if let Some(port) = self.port {
+            config.ibkr_gateway_port = port;
+        }
+        if let Some(ssl) = self.ssl {
+            config.ibkr_gateway_ssl = ssl;
+        }
+        if let Some(symbols) = self.symbols {
+            config.nasdaq_stocks = symbols
+                .iter()
+                .map(|s| s.trim().to_uppercase())
+                .filter(|s| !s.is_empty())
+                .collect();
+            config.watchlist = None;
+        }
+        if let Some(watchlist) = self.watchlist {
+            config.watchlist = Some(watchlist);
+        }
+        if let Some(dir) = self.watchlist_dir {
+            config.watchlist_dir = dir;
+        }
+        if let Some(min_profit) = self.min_profit {
+            config.min_profit_percent = min_profit;
+        }
+        if let Some(probability) = self.min_prob_itm {
+            config.min_prob_itm = probability;
+        }
+        if let Some(probability) = self.min_prob_touch {
+            config.min_prob_touch = probability;
+        }
+        if let Some(value) = self.min_expected_value {
+            config.min_expected_value = Some(value);
+        }
+        if let Some(sort_by) = self.sort_by {
+            config.sort_by = sort_by;
+        }
+        if let Some(filter) = self.filter_expr {
+            config.filter_expression = Some(filter);
+        }
+        if let Some(sort) = self.sort_expr {
+            config.sort_expression = Some(sort);
+        }
+        if let Some(strategies) = self.strategies {
+            config.strategies = strategies
+                .iter()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+        if let Some(volume) = self.min_volume {
+            config.filters.min_volume = Some(volume);
+        }
+        if let Some(open_interest) = self.min_open_interest {
+            config.filters.min_open_interest = Some(open_interest);
+        }
+        if let Some(days) = self.min_dte {
+            config.filters.min_days_to_expiry = Some(days);
+        }
+        if let Some(days) = self.max_dte {
+            config.filters.max_days_to_expiry = Some(days);
+        }
+        if let Some(moneyness) = self.min_moneyness {
+            config.filters.min_moneyness = Some(moneyness);
+        }
+        if let Some(moneyness) = self.max_moneyness {
+            config.filters.max_moneyness = Some(moneyness);
+        }
+        if let Some(delta) = self.min_delta {
+            config.filters.min_delta = Some(delta);
+        }
+        if let Some(delta) = self.max_delta {
+            config.filters.max_delta = Some(delta);
+        }
+        if let Some(spread) = self.max_spread_percent {
+            config.filters.max_spread_percent = Some(spread);
+        }
+        if let Some(premium) = self.min_premium {
+            config.filters.min_premium = Some(premium);
+        }
+        config.filters.validate().map_err(ConfigError::InvalidFilter)?;
+        if let Some(preset) = self.fee_schedule {
+            config.fees = FeeSchedule::from_preset(preset);
+        }
+        if let Some(commission) = self.commission_per_contract {
+            config.fees.commission_per_contract = commission;
+        }
+        if let Some(minimum) = self.commission_min {
+            config.fees.min_per_order = minimum;
+        }
+        if let Some(fee) = self.exchange_fee {
+            config.fees.exchange_fee_per_contract = fee;
+        }
+        if let Some(fee) = self.regulatory_fee {
+            config.fees.regulatory_fee_per_contract = fee;
+        }
+        if let Some(include) = self.include_spread_cost {
+            config.fees.include_spread = include;
+        }
+        if let Some(value) = self.cc_min_static_return {
+            config.covered_calls.min_static_return = value;
+        }
+        if let Some(value) = self.cc_min_if_called_return {
+            config.covered_calls.min_if_called_return = value;
+        }
+        if let Some(value) = self.cc_min_downside_protection {
+            config.covered_calls.min_downside_protection = value;
+        }
+        if let Some(value) = self.cc_min_annualized_yield {
+            config.covered_calls.min_annualized_yield = value;
+        }
+        if let Some(value) = self.csp_min_yield {
+            config.cash_secured_puts.min_yield = value;
+        }
+        if let Some(value) = self.csp_max_delta {
+            config.cash_secured_puts.max_delta = value;
+        }
+        if let Some(width) = self.spread_max_width {
+            config.vertical_spreads.max_width = width;
+        }
+        if let Some(credit) = self.spread_min_credit {
+            config.vertical_spreads.min_credit = credit;
+        }
+        if let Some(top) = self.spread_top_per_symbol {
+            config.vertical_spreads.top_per_symbol = top;
+        }
+        if let Some(width) = self.condor_wing_width {
+            config.neutral_strategies.wing_width = width;
+        }
+        if let Some(ratio) = self.min_move_ratio {
+            config.neutral_strategies.min_move_ratio = ratio;
+        }
+        if let Some(differential) = self.calendar_min_iv_diff {
+            config.calendar_spreads.min_iv_differential = differential;
+        }
+        if let Some(diagonals) = self.calendar_diagonals {
+            config.calendar_spreads.include_diagonals = diagonals;
+        }
+        if let Some(top) = self.calendar_top_per_symbol {
+            config.calendar_spreads.top_per_symbol = top;
+        }
+        if let Some(enabled) = self.ranking {
+            config.ranking.enabled = enabled;
+        }
+        if let Some(weight) = self.rank_return_weight {
+            config.ranking.return_weight = weight;
+        }
+        if let Some(weight) = self.rank_probability_weight {
+            config.ranking.probability_weight = weight;
+        }
+        if let Some(weight) = self.rank_liquidity_weight {
+            config.ranking.liquidity_weight = weight;
+        }
+        if let Some(weight) = self.rank_iv_weight {
+            config.ranking.iv_rank_weight = weight;
+        }
+        if let Some(top_n) = self.top_n {
+            config.ranking.top_n = top_n;
+        }
+        if let Some(top) = self.top_per_symbol {
+            config.ranking.top_per_symbol = top;
+        }
+        if let Some(enabled) = self.cycle_diff {
+            config.cycle_diff.enabled = enabled;
+        }
+        if let Some(change) = self.diff_min_price_change {
+            config.cycle_diff.min_price_change_percent = change;
+        }
+        if let Some(change) = self.diff_min_profit_change {
+            config.cycle_diff.min_profit_change = change;
+        }
+        if let Some(change) = self.diff_min_iv_change {
+            config.cycle_diff.min_iv_change = change;
+        }
+        if let Some(rate) = self.risk_free_rate {
+            config.risk_free_rate = rate;
+        }
+        if let Some(dividend_yield) = self.dividend_yield {
+            config.dividend_yield = dividend_yield;
+        }
+        if let Some(steps) = self.tree_steps {
+            config.tree_steps = steps;
+        }
+        if let Some(interval) = self.scan_interval {
+            config.scan_interval_seconds = interval;
+        }
+        if let Some(interval) = self.tickle_interval {
+            config.tickle_interval_seconds = tickle_interval("--tickle-interval", interval)?;
+        }
+        if let Some(attempts) = self.max_retry_attempts {
+            config.max_retry_attempts = attempts;
+        }
+        if let Some(delay) = self.retry_base_delay_ms {
+            config.retry_base_delay_ms = delay;
+        }
+        if let Some(delay) = self.retry_max_delay_ms {
+            config.retry_max_delay_ms = delay;
+        }
+        if let Some(timeout) = self.timeout {
+            config.request_timeout_seconds = timeout;
+        }
+        if let Some(rate) = self.rate_limit {
+            config.rate_limit_per_second = positive_rate("--rate-limit", rate)?;
+        }
+        if let Some(limits) = self.endpoint_rate_limits {
+            config.endpoint_rate_limits = EndpointRateLimit::parse_list(&limits)?;
+        }
+        if let Some(max_concurrent) = self.max_concurrent {
+            config.max_concurrent_symbols = max_concurrent;
+        }
+        if let Some(strike_count) = self.strike_count {
+            config.strike_count = strike_count;
+        }
+        if let Some(months) = self.option_months {
+            config.option_months = months;
+        }
+        if let Some(account_id) = self.account_id {
+            config.ibkr_account_id = account_id;
+        }
+        if let Some(path) = self.log_file {
+            config.log_file_path = path;
+        }
+        if let Some(dir) = self.options_log_dir {
+            config.options_log_dir = dir;
+        }
+        if let Some(path) = self.conid_cache {
+            config.conid_cache_path = path;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides(args: &[&str]) -> ConfigOverrides {
+        let args = std::iter::once("ibkr_nasdaq_scanner").chain(args.iter().copied());
+        Cli::try_parse_from(args).unwrap().overrides
+    }
+
+    fn applied(args: &[&str]) -> Result<ScannerConfig, ConfigError> {
+        let mut config = ScannerConfig::from_env()?;
+        overrides(args).apply(&mut config)?;
+        Ok(config)
+    }
+
+    #[test]
+    fn test_flags_override_config_fields() {
+        let config = applied(&[
+            "scan",
+            "--once",
+            "--host",
+            "gateway.local",
+            "--port",
+            "5001",
+            "--symbols",
+            "aapl, msft,",
+            "--max-dte",
+            "45",
+            "--min-moneyness",
+            "-2.5",
+            "--sort-by",
+            "annualized",
+            "--strategies",
+            "long_options,covered_calls",
+            "--rate-limit",
+            "2.5",
+            "--endpoint-rate-limits",
+            "/iserver/secdef=1",
+            "--tickle-interval",
+            "30",
+            "--max-retry-attempts",
+            "5",
+            "--account-id",
+            "U7654321",
+        ])
+        .unwrap();
+
+        assert_eq!(config.base_url(), "http://gateway.local:5001");
+        assert_eq!(config.nasdaq_stocks, vec!["AAPL", "MSFT"]);
+        assert_eq!(config.watchlist, None);
+        assert_eq!(config.filters.max_days_to_expiry, Some(45.0));
+        assert_eq!(config.filters.min_moneyness, Some(-2.5));
+        assert_eq!(config.sort_by, SortKey::Annualized);
+        assert_eq!(config.strategies, vec!["long_options", "covered_calls"]);
+        assert_eq!(config.rate_limit_per_second, 2.5);
+        assert_eq!(config.endpoint_rate_limits.len(), 1);
+        assert_eq!(config.tickle_interval_seconds, 30);
+        assert_eq!(config.max_retry_attempts, 5);
+        assert_eq!(config.ibkr_account_id, "U7654321");
+    }
+
+    #[test]
+    fn test_unset_flags_keep_config_values() {
+        let before = ScannerConfig::from_env().unwrap();
+        let after = applied(&["config", "show"]).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&before).unwrap(),
+            serde_json::to_value(&after).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_overrides_are_rejected() {
+        assert!(matches!(
+            applied(&["--min-delta", "0.5", "--max-delta", "0.2"]),
+            Err(ConfigError::InvalidFilter(_))
+        ));
+        assert!(matches!(
+            applied(&["--rate-limit", "0"]),
+            Err(ConfigError::InvalidRateLimit(_))
+        ));
+        assert!(matches!(
+            applied(&["--tickle-interval", "0"]),
+            Err(ConfigError::InvalidInterval(_))
+        ));
+        assert!(Cli::try_parse_from(["ibkr_nasdaq_scanner", "--filter-expr", "dte >"]).is_err());
+    }
+}
@@ -1,26 +1,115 @@
-mod api;
-mod models;
-mod scanner;
-mod utils;
+mod cli;
 
-use dotenv::dotenv;
-use env_logger;
+use clap::Parser;
+use cli::{Cli, Command, ConfigCommand};
+use ibkr_nasdaq_scanner::models::{ScannerConfig, Watchlist};
+use ibkr_nasdaq_scanner::scanner;
+use ibkr_nasdaq_scanner::scanner::strategy::StrategyRegistry;
+use ibkr_nasdaq_scanner::utils::helpers::mask;
 use log::{error, info};
 use std::process;
 
 #[tokio::main]
 async fn main() {
+    let cli = Cli::parse();
+
     // Initialize environment variables
-    dotenv().ok();
-    
+    match &cli.env_file {
+        Some(path) => {
+            if let Err(e) = dotenv::from_path(path) {
+                eprintln!("Failed to load env file {}: {}", path.display(), e);
+                process::exit(1);
+            }
+        }
+        None => {
+            dotenv::dotenv().ok();
+        }
+    }
+
     // Initialize logger
     env_logger::init();
-    
-    info!("Starting IBKR NASDAQ Option Scanner");
-    
-    // Run the scanner - fixed path
-    if let Err(e) = scanner::nasdaq_scanner::run().await {
-        error!("Scanner failed: {}", e);
+
+    let mut config = match ScannerConfig::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Invalid configuration: {}", e);
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = cli.overrides.apply(&mut config) {
+        error!("Invalid configuration: {}", e);
         process::exit(1);
     }
+
+    let strategies = StrategyRegistry::builtin(&config);
+
+    match cli.command.unwrap_or(Command::Watch) {
+        Command::Scan { once: true } => {
+            info!("Running a single scan of {} symbols", config.nasdaq_stocks.len());
+
+            if let Err(e) = scanner::run_once(config, strategies).await {
+                error!("Scan failed: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::Scan { once: false } | Command::Watch => {
+            info!("Starting IBKR NASDAQ Option Scanner");
+
+            if let Err(e) = scanner::run(config, strategies).await {
+                error!("Scanner failed: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::CheckConnection => match scanner::check_connection(&config).await {
+            Ok(state) => {
+                println!("Gateway {} is reachable, session is {}", config.base_url(), state);
+                if !state.is_ready() {
+                    process::exit(2);
+                }
+            }
+            Err(e) => {
+                println!("Gateway {} check failed: {}", config.base_url(), e);
+                process::exit(1);
+            }
+        },
+        Command::Watchlists { show: false } => {
+            for name in Watchlist::available(&config.watchlist_dir) {
+                println!("{}", name);
+            }
+        }
+        Command::Watchlists { show: true } => match scanner::load_watchlist(&config) {
+            Ok(watchlist) => {
+                println!("{} ({} symbols)", watchlist.name, watchlist.entries.len());
+                for entry in &watchlist.entries {
+                    println!("{}", entry.symbol);
+                }
+            }
+            Err(e) => {
+                error!("{}", e);
+                process::exit(1);
+            }
+        },
+        Command::Strategies => {
+            for name in strategies.names() {
+                let marker = if config.strategies.iter().any(|s| s == name) { "*" } else { " " };
+                println!("{} {}", marker, name);
+            }
+        }
+        Command::Config { action: ConfigCommand::Show } => {
+            // Output ends up in terminals and CI logs, so keep the account ID out of it
+            let shown = ScannerConfig {
+                ibkr_account_id: mask(&config.ibkr_account_id, 3),
+                ..config
+            };
+            
+            match serde_json::to_string_pretty(&shown) {
+                Ok(json) => println!("{}", json),
+                Err(e) => {
+                    error!("Failed to render configuration: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,927 @@
+use crate::models::OptionChain;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+// A parse error with the 1-based column it was found at
+#[derive(Debug, Clone, Error, PartialEq)]
+#[error("{message} at column {column}")]
+pub struct ExpressionError {
+    pub message: String,
+    pub column: usize,
+}
+
+impl ExpressionError {
+    fn new(message: impl Into<String>, column: usize) -> Self {
+        Self {
+            message: message.into(),
+            column,
+        }
+    }
+}
+
+// Fields available to expressions. Percentages (profit, return, annualized, iv, the
+// probabilities, spread_pct, moneyness) are in percent, as in the results table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Profit,
+    Premium,
+    Bid,
+    Ask,
+    Mid,
+    Last,
+    Strike,
+    Spot,
+    Volume,
+    OpenInterest,
+    Iv,
+    Delta,
+    Gamma,
+    Theta,
+    Vega,
+    Rho,
+    Dte,
+    Breakeven,
+    MaxGain,
+    MaxLoss,
+    Return,
+    Annualized,
+    ProbItm,
+    ProbProfit,
+    ProbTouch,
+    Ev,
+    Cost,
+    Theoretical,
+    SpreadPct,
+    Moneyness,
+    Score,
+}
+
+impl Field {
+    const NAMES: [&'static str; 31] = [
+        "profit",
+        "premium",
+        "bid",
+        "ask",
+        "mid",
+        "last",
+        "strike",
+        "spot",
+        "volume",
+        "oi",
+        "iv",
+        "delta",
+        "gamma",
+        "theta",
+        "vega",
+        "rho",
+        "dte",
+        "breakeven",
+        "max_gain",
+        "max_loss",
+        "return",
+        "annualized",
+        "prob_itm",
+        "prob_profit",
+        "prob_touch",
+        "ev",
+        "cost",
+        "theoretical",
+        "spread_pct",
+        "moneyness",
+        "score",
+    ];
+
+    fn parse(name: &str) -> Option<Self> {
+        let field = match name {
+            "profit" | "profit_percent" => Field::Profit,
+            "premium" | "price" => Field::Premium,
+            "bid" => Field::Bid,
+            "ask" => Field::Ask,
+            "mid" => Field::Mid,
+            "last" => Field::Last,
+            "strike" => Field::Strike,
+            "spot" | "stock_price" => Field::Spot,
+            "volume" => Field::Volume,
+            "oi" | "open_interest" => Field::OpenInterest,
+            "iv" => Field::Iv,
+            "delta" => Field::Delta,
+            "gamma" => Field::Gamma,
+            "theta" => Field::Theta,
+            "vega" => Field::Vega,
+            "rho" => Field::Rho,
+            "dte" | "days_to_expiry" => Field::Dte,
+            "breakeven" => Field::Breakeven,
+            "max_gain" => Field::MaxGain,
+            "max_loss" => Field::MaxLoss,
+            "return" | "roc" => Field::Return,
+            "annualized" => Field::Annualized,
+            "prob_itm" => Field::ProbItm,
+            "prob_profit" => Field::ProbProfit,
+            "prob_touch" => Field::ProbTouch,
+            "ev" | "expected_value" => Field::Ev,
+            "cost" | "trading_cost" => Field::Cost,
+            "theoretical" => Field::Theoretical,
+            "spread_pct" => Field::SpreadPct,
+            "moneyness" => Field::Moneyness,
+            "score" => Field::Score,
+            _ => return None,
+        };
+
+        Some(field)
+    }
+
+    fn value(&self, chain: &OptionChain) -> f64 {
+        match self {
+            Field::Profit => chain.profit_percent,
+            Field::Premium => chain.option_price,
+            Field::Bid => chain.bid,
+            Field::Ask => chain.ask,
+            Field::Mid => chain.mid_price(),
+            Field::Last => chain.last,
+            Field::Strike => chain.strike,
+            Field::Spot => chain.stock_price,
+            Field::Volume => chain.volume as f64,
+            Field::OpenInterest => chain.open_interest as f64,
+            Field::Iv => chain.implied_volatility * 100.0,
+            Field::Delta => chain.delta,
+            Field::Gamma => chain.gamma,
+            Field::Theta => chain.theta,
+            Field::Vega => chain.vega,
+            Field::Rho => chain.rho,
+            Field::Dte => chain.metrics.days_to_expiry,
+            Field::Breakeven => chain.metrics.breakeven,
+            Field::MaxGain => chain.metrics.max_gain.unwrap_or(f64::INFINITY),
+            Field::MaxLoss => chain.metrics.max_loss,
+            Field::Return => chain.metrics.return_on_capital,
+            Field::Annualized => chain.metrics.annualized_return,
+            Field::ProbItm => chain.prob_itm * 100.0,
+            Field::ProbProfit => chain.prob_profit * 100.0,
+            Field::ProbTouch => chain.prob_touch * 100.0,
+            Field::Ev => chain.expected_value,
+            Field::Cost => chain.trading_cost,
+            Field::Theoretical => chain.theoretical_value,
+            // No two-sided quote reads as an infinitely wide spread
+            Field::SpreadPct => chain.spread_percent().unwrap_or(f64::INFINITY),
+            Field::Moneyness => chain.moneyness(),
+            // Only set once the cycle is ranked; filters reject it at parse time
+            Field::Score => chain.score.unwrap_or(0.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(usize, usize), // byte range into the source
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LeftParen,
+    RightParen,
+    Comma,
+    End,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExpressionError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let column = source[..start].chars().count() + 1;
+        let c = bytes[i] as char;
+
+        let token = match c {
+            ' ' | '\t' | '\n' | '\r' => {
+                i += 1;
+                continue;
+            }
+            '0'..='9' | '.' => {
+                while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                    i += 1;
+                }
+                let text = &source[start..i];
+                let value = text.parse().map_err(|_| {
+                    ExpressionError::new(format!("invalid number '{}'", text), column)
+                })?;
+                tokens.push((Token::Number(value), column));
+                continue;
+            }
+            'a'..='z' | 'A'..='Z' | '_' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                tokens.push((Token::Ident(start, i), column));
+                continue;
+            }
+            '<' if bytes.get(i + 1) == Some(&b'=') => Token::LessEqual,
+            '>' if bytes.get(i + 1) == Some(&b'=') => Token::GreaterEqual,
+            '=' if bytes.get(i + 1) == Some(&b'=') => Token::Equal,
+            '!' if bytes.get(i + 1) == Some(&b'=') => Token::NotEqual,
+            '<' => Token::Less,
+            '>' => Token::Greater,
+            '=' => Token::Equal,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+            '/' => Token::Slash,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            _ => {
+                let unexpected = source[start..].chars().next().unwrap_or(c);
+                return Err(ExpressionError::new(
+                    format!("unexpected character '{}'", unexpected),
+                    column,
+                ));
+            }
+        };
+
+        i += match token {
+            Token::LessEqual | Token::GreaterEqual | Token::NotEqual => 2,
+            Token::Equal if bytes.get(i + 1) == Some(&b'=') => 2,
+            _ => 1,
+        };
+        tokens.push((token, column));
+    }
+
+    tokens.push((Token::End, source.chars().count() + 1));
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Compare {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Arithmetic {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f64),
+    Field(Field),
+    Abs(Box<Node>),
+    Negate(Box<Node>),
+    Arithmetic(Box<Node>, Arithmetic, Box<Node>),
+    Compare(Box<Node>, Compare, Box<Node>),
+    Between(Box<Node>, Box<Node>, Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Not(Box<Node>),
+}
+
+impl Node {
+    fn is_condition(&self) -> bool {
+        matches!(
+            self,
+            Node::Compare(..) | Node::Between(..) | Node::And(..) | Node::Or(..) | Node::Not(..)
+        )
+    }
+
+    // Conditions evaluate to 1 or 0; the parser keeps them out of arithmetic
+    fn value(&self, chain: &OptionChain) -> f64 {
+        match self {
+            Node::Number(value) => *value,
+            Node::Field(field) => field.value(chain),
+            Node::Abs(inner) => inner.value(chain).abs(),
+            Node::Negate(inner) => -inner.value(chain),
+            Node::Arithmetic(left, op, right) => {
+                let (left, right) = (left.value(chain), right.value(chain));
+                match op {
+                    Arithmetic::Add => left + right,
+                    Arithmetic::Subtract => left - right,
+                    Arithmetic::Multiply => left * right,
+                    Arithmetic::Divide => left / right,
+                }
+            }
+            _ => {
+                if self.holds(chain) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    fn holds(&self, chain: &OptionChain) -> bool {
+        match self {
+            Node::Compare(left, op, right) => {
+                let (left, right) = (left.value(chain), right.value(chain));
+                match op {
+                    Compare::Less => left < right,
+                    Compare::LessEqual => left <= right,
+                    Compare::Greater => left > right,
+                    Compare::GreaterEqual => left >= right,
+                    Compare::Equal => left == right,
+                    Compare::NotEqual => left != right,
+                }
+            }
+            Node::Between(value, low, high) => {
+                let value = value.value(chain);
+                value >= low.value(chain) && value <= high.value(chain)
+            }
+            Node::And(left, right) => left.holds(chain) && right.holds(chain),
+            Node::Or(left, right) => left.holds(chain) || right.holds(chain),
+            Node::Not(inner) => !inner.holds(chain),
+            _ => self.value(chain) != 0.0,
+        }
+    }
+}
+
+// Recursive descent, loosest binding first: or, and, not, comparison, + -, * /, unary
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    filtering: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str, filtering: bool) -> Result<Self, ExpressionError> {
+        Ok(Self {
+            source,
+            tokens: tokenize(source)?,
+            position: 0,
+            filtering,
+        })
+    }
+
+    fn peek(&self) -> Token {
+        self.tokens[self.position].0
+    }
+
+    fn column(&self) -> usize {
+        self.tokens[self.position].1
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.peek();
+        if token != Token::End {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn word(&self, token: Token) -> Option<String> {
+        match token {
+            Token::Ident(start, end) => Some(self.source[start..end].to_lowercase()),
+            _ => None,
+        }
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        self.word(self.peek()).as_deref() == Some(keyword)
+    }
+
+    fn describe(&self, token: Token) -> String {
+        match token {
+            Token::End => "end of expression".to_string(),
+            Token::Number(value) => format!("number {}", value),
+            Token::Ident(start, end) => format!("'{}'", &self.source[start..end]),
+            Token::Less => "'<'".to_string(),
+            Token::LessEqual => "'<='".to_string(),
+            Token::Greater => "'>'".to_string(),
+            Token::GreaterEqual => "'>='".to_string(),
+            Token::Equal => "'='".to_string(),
+            Token::NotEqual => "'!='".to_string(),
+            Token::Plus => "'+'".to_string(),
+            Token::Minus => "'-'".to_string(),
+            Token::Star => "'*'".to_string(),
+            Token::Slash => "'/'".to_string(),
+            Token::LeftParen => "'('".to_string(),
+            Token::RightParen => "')'".to_string(),
+            Token::Comma => "','".to_string(),
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> ExpressionError {
+        ExpressionError::new(
+            format!("expected {}, found {}", expected, self.describe(self.peek())),
+            self.column(),
+        )
+    }
+
+    fn condition(&mut self) -> Result<Node, ExpressionError> {
+        let column = self.column();
+        let node = self.or()?;
+
+        if node.is_condition() {
+            Ok(node)
+        } else {
+            Err(ExpressionError::new(
+                "expected a condition such as 'profit > 5'",
+                column,
+            ))
+        }
+    }
+
+    fn value(&mut self) -> Result<Node, ExpressionError> {
+        let column = self.column();
+        let node = self.or()?;
+
+        if node.is_condition() {
+            Err(ExpressionError::new(
+                "expected a value, found a condition",
+                column,
+            ))
+        } else {
+            Ok(node)
+        }
+    }
+
+    fn or(&mut self) -> Result<Node, ExpressionError> {
+        let mut left = self.and()?;
+
+        while self.at_keyword("or") {
+            let column = self.column();
+            self.advance();
+            let right = self.and()?;
+            left = Node::Or(
+                Box::new(Self::require_condition(left, column)?),
+                Box::new(Self::require_condition(right, column)?),
+            );
+        }
+
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Node, ExpressionError> {
+        let mut left = self.not()?;
+
+        while self.at_keyword("and") {
+            let column = self.column();
+            self.advance();
+            let right = self.not()?;
+            left = Node::And(
+                Box::new(Self::require_condition(left, column)?),
+                Box::new(Self::require_condition(right, column)?),
+            );
+        }
+
+        Ok(left)
+    }
+
+    fn not(&mut self) -> Result<Node, ExpressionError> {
+        if self.at_keyword("not") {
+            let column = self.column();
+            self.advance();
+            let inner = self.not()?;
+            return Ok(Node::Not(Box::new(Self::require_condition(inner, column)?)));
+        }
+
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Node, ExpressionError> {
+        let left = self.sum()?;
+        let column = self.column();
+
+        let op = match self.peek() {
+            Token::Less => Compare::Less,
+            Token::LessEqual => Compare::LessEqual,
+            Token::Greater => Compare::Greater,
+            Token::GreaterEqual => Compare::GreaterEqual,
+            Token::Equal => Compare::Equal,
+            Token::NotEqual => Compare::NotEqual,
+            _ if self.at_keyword("between") => {
+                self.advance();
+                let low = self.sum()?;
+                if !self.at_keyword("and") {
+                    return Err(self.unexpected("'and' after the lower bound of 'between'"));
+                }
+                self.advance();
+                let high = self.sum()?;
+
+                return Ok(Node::Between(
+                    Box::new(Self::require_value(left, column)?),
+                    Box::new(Self::require_value(low, column)?),
+                    Box::new(Self::require_value(high, column)?),
+                ));
+            }
+            _ => return Ok(left),
+        };
+
+        self.advance();
+        let right = self.sum()?;
+
+        Ok(Node::Compare(
+            Box::new(Self::require_value(left, column)?),
+            op,
+            Box::new(Self::require_value(right, column)?),
+        ))
+    }
+
+    fn sum(&mut self) -> Result<Node, ExpressionError> {
+        let mut left = self.product()?;
+
+        loop {
+            let op = match self.peek() {
+                Token::Plus => Arithmetic::Add,
+                Token::Minus => Arithmetic::Subtract,
+                _ => return Ok(left),
+            };
+            let column = self.column();
+            self.advance();
+            let right = self.product()?;
+            left = Node::Arithmetic(
+                Box::new(Self::require_value(left, column)?),
+                op,
+                Box::new(Self::require_value(right, column)?),
+            );
+        }
+    }
+
+    fn product(&mut self) -> Result<Node, ExpressionError> {
+        let mut left = self.unary()?;
+
+        loop {
+            let op = match self.peek() {
+                Token::Star => Arithmetic::Multiply,
+                Token::Slash => Arithmetic::Divide,
+                _ => return Ok(left),
+            };
+            let column = self.column();
+            self.advance();
+            let right = self.unary()?;
+            left = Node::Arithmetic(
+                Box::new(Self::require_value(left, column)?),
+                op,
+                Box::new(Self::require_value(right, column)?),
+            );
+        }
+    }
+
+    fn unary(&mut self) -> Result<Node, ExpressionError> {
+        if self.peek() == Token::Minus {
+            let column = self.column();
+            self.advance();
+            let inner = self.unary()?;
+            return Ok(Node::Negate(Box::new(Self::require_value(inner, column)?)));
+        }
+
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Node, ExpressionError> {
+        let column = self.column();
+
+        match self.peek() {
+            Token::Number(value) => {
+                self.advance();
+                Ok(Node::Number(value))
+            }
+            Token::LeftParen => {
+                self.advance();
+                let inner = self.or()?;
+                self.expect_close()?;
+                Ok(inner)
+            }
+            token @ Token::Ident(..) => {
+                self.advance();
+                let name = self.word(token).unwrap_or_default();
+
+                if name == "abs" {
+                    if self.advance() != Token::LeftParen {
+                        return Err(ExpressionError::new("expected '(' after abs", column));
+                    }
+                    let inner = self.value()?;
+                    self.expect_close()?;
+                    return Ok(Node::Abs(Box::new(inner)));
+                }
+
+                let field = Field::parse(&name).ok_or_else(|| {
+                    ExpressionError::new(
+                        format!(
+                            "unknown field '{}', expected one of: {}",
+                            name,
+                            Field::NAMES.join(", ")
+                        ),
+                        column,
+                    )
+                })?;
+
+                // Filters run before ranking, where every score would still read 0
+                if self.filtering && field == Field::Score {
+                    return Err(ExpressionError::new(
+                        "'score' is only set after ranking and can't be used in a filter, \
+                         only in SORT_EXPR",
+                        column,
+                    ));
+                }
+
+                Ok(Node::Field(field))
+            }
+            _ => Err(self.unexpected("a field, number or '('")),
+        }
+    }
+
+    fn expect_close(&mut self) -> Result<(), ExpressionError> {
+        if self.peek() == Token::RightParen {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.unexpected("')'"))
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), ExpressionError> {
+        if self.peek() == Token::End {
+            Ok(())
+        } else {
+            Err(self.unexpected("end of expression"))
+        }
+    }
+
+    fn require_condition(node: Node, column: usize) -> Result<Node, ExpressionError> {
+        if node.is_condition() {
+            Ok(node)
+        } else {
+            Err(ExpressionError::new(
+                "'and', 'or' and 'not' need conditions on both sides",
+                column,
+            ))
+        }
+    }
+
+    fn require_value(node: Node, column: usize) -> Result<Node, ExpressionError> {
+        if node.is_condition() {
+            Err(ExpressionError::new(
+                "conditions can't be compared or used in arithmetic",
+                column,
+            ))
+        } else {
+            Ok(node)
+        }
+    }
+}
+
+// A condition over option fields, e.g. "dte between 7 and 30 and spread_pct < 5"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FilterExpression {
+    source: String,
+    root: Node,
+}
+
+impl FilterExpression {
+    pub fn matches(&self, chain: &OptionChain) -> bool {
+        self.root.holds(chain)
+    }
+}
+
+impl FromStr for FilterExpression {
+    type Err = ExpressionError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(source, true)?;
+
+        if parser.peek() == Token::End {
+            return Err(ExpressionError::new("empty expression", 1));
+        }
+
+        let root = parser.condition()?;
+        parser.expect_end()?;
+
+        Ok(Self {
+            source: source.trim().to_string(),
+            root,
+        })
+    }
+}
+
+impl TryFrom<String> for FilterExpression {
+    type Error = ExpressionError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        source.parse()
+    }
+}
+
+impl From<FilterExpression> for String {
+    fn from(expression: FilterExpression) -> Self {
+        expression.source
+    }
+}
+
+impl fmt::Display for FilterExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+// Comma-separated sort keys, each a value with an optional asc/desc (default desc, best
+// first), e.g. "prob_profit * annualized desc, dte asc"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SortExpression {
+    source: String,
+    keys: Vec<(Node, bool)>, // (value, ascending)
+}
+
+impl SortExpression {
+    pub fn sort(&self, options: &mut [OptionChain]) {
+        options.sort_by(|a, b| {
+            self.keys
+                .iter()
+                .map(|(key, ascending)| {
+                    let ordering = key.value(a).total_cmp(&key.value(b));
+                    if *ascending {
+                        ordering
+                    } else {
+                        ordering.reverse()
+                    }
+                })
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+}
+
+impl FromStr for SortExpression {
+    type Err = ExpressionError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(source, false)?;
+        let mut keys = Vec::new();
+
+        if parser.peek() == Token::End {
+            return Err(ExpressionError::new("empty expression", 1));
+        }
+
+        loop {
+            let key = parser.value()?;
+
+            let ascending = if parser.at_keyword("asc") {
+                parser.advance();
+                true
+            } else {
+                if parser.at_keyword("desc") {
+                    parser.advance();
+                }
+                false
+            };
+            keys.push((key, ascending));
+
+            match parser.peek() {
+                Token::Comma => {
+                    parser.advance();
+                }
+                Token::End => break,
+                _ => return Err(parser.unexpected("',', 'asc', 'desc' or end of expression")),
+            }
+        }
+
+        Ok(Self {
+            source: source.trim().to_string(),
+            keys,
+        })
+    }
+}
+
+impl TryFrom<String> for SortExpression {
+    type Error = ExpressionError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        source.parse()
+    }
+}
+
+impl From<SortExpression> for String {
+    fn from(expression: SortExpression) -> Self {
+        expression.source
+    }
+}
+
+impl fmt::Display for SortExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{OptionMetrics, OptionType};
+
+    fn option(strike: f64, dte: f64, annualized: f64) -> OptionChain {
+        OptionChain {
+            bid: 1.96,
+            ask: 2.04,
+            last: 2.0,
+            profit_percent: 4.0,
+            volume: 250,
+            open_interest: 1_000,
+            implied_volatility: 0.35,
+            delta: -0.4,
+            prob_itm: 0.4,
+            prob_profit: 0.3,
+            metrics: OptionMetrics {
+                days_to_expiry: dte,
+                annualized_return: annualized,
+                ..OptionMetrics::default()
+            },
+            ..OptionChain::sample(OptionType::Call, strike, 2.0)
+        }
+    }
+
+    fn filter(source: &str) -> FilterExpression {
+        source.parse().unwrap()
+    }
+
+    #[test]
+    fn test_filter_example_from_docs() {
+        let expression = filter("dte between 7 and 30 and spread_pct < 5 and annualized > 20");
+
+        assert!(expression.matches(&option(100.0, 14.0, 35.0)));
+        assert!(!expression.matches(&option(100.0, 45.0, 35.0)));
+        assert!(!expression.matches(&option(100.0, 14.0, 10.0)));
+    }
+
+    #[test]
+    fn test_precedence_and_functions() {
+        let chain = option(105.0, 14.0, 35.0);
+
+        // 'and' binds tighter than 'or'; '*' tighter than '+'
+        assert!(filter("volume < 10 and oi < 10 or iv > 30").matches(&chain));
+        assert!(!filter("volume < 10 and (oi < 10 or iv > 30)").matches(&chain));
+        assert!(filter("strike - spot * 2 = -95").matches(&chain));
+        assert!(filter("abs(delta) >= 0.4 and not moneyness < 0").matches(&chain));
+        assert!(filter("-delta = 0.4 AND prob_itm != prob_profit").matches(&chain));
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_problem() {
+        let error = "dte > 7 and volatility < 50".parse::<FilterExpression>().unwrap_err();
+        assert_eq!(error.column, 13);
+        assert!(error.message.contains("unknown field 'volatility'"));
+
+        let error = "dte between 7 30".parse::<FilterExpression>().unwrap_err();
+        assert_eq!(error.column, 15);
+        assert!(error.message.contains("'and'"));
+
+        let error = "(dte > 7".parse::<FilterExpression>().unwrap_err();
+        assert_eq!(error.to_string(), "expected ')', found end of expression at column 9");
+
+        assert!("annualized".parse::<FilterExpression>().is_err());
+        assert!("dte > 7 and 5".parse::<FilterExpression>().is_err());
+        assert!("(dte > 7) + 1 > 2".parse::<FilterExpression>().is_err());
+        assert!("dte # 7".parse::<FilterExpression>().is_err());
+        assert!("".parse::<FilterExpression>().is_err());
+
+        let error = "dte > 7 and score > 50".parse::<FilterExpression>().unwrap_err();
+        assert_eq!(error.column, 13);
+        assert!(error.message.contains("only set after ranking"));
+        assert!("score desc, dte asc".parse::<SortExpression>().is_ok());
+    }
+
+    #[test]
+    fn test_sort_expression_keys() {
+        let mut options = vec![
+            option(100.0, 30.0, 20.0),
+            option(105.0, 7.0, 20.0),
+            option(110.0, 14.0, 50.0),
+        ];
+
+        let sort: SortExpression = "annualized, dte asc".parse().unwrap();
+        sort.sort(&mut options);
+
+        let strikes: Vec<f64> = options.iter().map(|o| o.strike).collect();
+        assert_eq!(strikes, vec![110.0, 105.0, 100.0]);
+
+        assert!("dte > 7".parse::<SortExpression>().is_err());
+        assert!("dte up".parse::<SortExpression>().is_err());
+    }
+
+    #[test]
+    fn test_round_trips_through_serde() {
+        let expression = filter("profit >= 2");
+        let json = serde_json::to_string(&expression).unwrap();
+
+        assert_eq!(json, "\"profit >= 2\"");
+        assert_eq!(serde_json::from_str::<FilterExpression>(&json).unwrap(), expression);
+        assert!(serde_json::from_str::<FilterExpression>("\"profit >=\"").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+// IBKR Pro US option commissions for premiums of $0.10 and up; cheaper contracts get a
+// reduced per-contract rate (see `commission_rate`). Both plans add a $1.00 order minimum.
+const IBKR_COMMISSION_PER_CONTRACT: f64 = 0.65;
+const IBKR_MIN_PER_ORDER: f64 = 1.00;
+// Tiered passes exchange and clearing fees through; fixed bundles exchange fees
+const IBKR_TIERED_EXCHANGE_FEE: f64 = 0.20;
+// ORF, OCC clearing and FINRA TAF, approximately, per contract
+const IBKR_REGULATORY_FEE: f64 = 0.05;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeePreset {
+    #[default]
+    IbkrFixed,
+    IbkrTiered,
+    Custom,
+}
+
+impl fmt::Display for FeePreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeePreset::IbkrFixed => write!(f, "ibkr_fixed"),
+            FeePreset::IbkrTiered => write!(f, "ibkr_tiered"),
+            FeePreset::Custom => write!(f, "custom"),
+        }
+    }
+}
+
+impl FromStr for FeePreset {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "ibkr_fixed" | "fixed" => Ok(FeePreset::IbkrFixed),
+            "ibkr_tiered" | "tiered" => Ok(FeePreset::IbkrTiered),
+            "custom" => Ok(FeePreset::Custom),
+            other => Err(format!(
+                "unknown fee schedule '{}', expected ibkr_fixed, ibkr_tiered or custom",
+                other
+            )),
+        }
+    }
+}
+
+// Costs of trading one option contract, in dollars
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeeSchedule {
+    pub preset: FeePreset,
+    pub commission_per_contract: f64,
+    pub min_per_order: f64,
+    pub exchange_fee_per_contract: f64,
+    pub regulatory_fee_per_contract: f64,
+    pub include_spread: bool,
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        Self::from_preset(FeePreset::default())
+    }
+}
+
+impl FeeSchedule {
+    pub fn from_preset(preset: FeePreset) -> Self {
+        let (commission, minimum, exchange, regulatory) = match preset {
+            FeePreset::IbkrFixed => (
+                IBKR_COMMISSION_PER_CONTRACT,
+                IBKR_MIN_PER_ORDER,
+                0.0,
+                IBKR_REGULATORY_FEE,
+            ),
+            FeePreset::IbkrTiered => (
+                IBKR_COMMISSION_PER_CONTRACT,
+                IBKR_MIN_PER_ORDER,
+                IBKR_TIERED_EXCHANGE_FEE,
+                IBKR_REGULATORY_FEE,
+            ),
+            FeePreset::Custom => (0.0, 0.0, 0.0, 0.0),
+        };
+
+        Self {
+            preset,
+            commission_per_contract: commission,
+            min_per_order: minimum,
+            exchange_fee_per_contract: exchange,
+            regulatory_fee_per_contract: regulatory,
+            include_spread: true,
+        }
+    }
+
+    // IBKR charges less per contract on very cheap options; custom schedules are flat
+    fn commission_rate(&self, premium: f64) -> f64 {
+        match self.preset {
+            FeePreset::Custom => self.commission_per_contract,
+            _ if premium < 0.05 => self.commission_per_contract.min(0.25),
+            _ if premium < 0.10 => self.commission_per_contract.min(0.50),
+            _ => self.commission_per_contract,
+        }
+    }
+
+    // Commission plus exchange and regulatory fees for one order of `contracts`
+    pub fn order_cost(&self, contracts: u32, premium: f64) -> f64 {
+        let contracts = contracts as f64;
+        let commission = (self.commission_rate(premium) * contracts).max(self.min_per_order);
+
+        commission
+            + (self.exchange_fee_per_contract + self.regulatory_fee_per_contract) * contracts
+    }
+
+    // Opening and closing one contract, plus crossing the bid/ask spread (half each way)
+    pub fn round_trip_cost(&self, premium: f64, bid: f64, ask: f64, multiplier: f64) -> f64 {
+        let spread = if self.include_spread && bid > 0.0 && ask > bid {
+            (ask - bid) * multiplier
+        } else {
+            0.0
+        };
+
+        2.0 * self.order_cost(1, premium) + spread
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_preset_applies_order_minimum() {
+        let fees = FeeSchedule::from_preset(FeePreset::IbkrFixed);
+
+        // One contract: $0.65 commission is raised to the $1.00 minimum
+        assert!((fees.order_cost(1, 2.50) - 1.05).abs() < 1e-9);
+        assert!((fees.order_cost(10, 2.50) - 7.00).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cheap_premiums_get_reduced_rate() {
+        let fees = FeeSchedule {
+            min_per_order: 0.0,
+            regulatory_fee_per_contract: 0.0,
+            ..FeeSchedule::from_preset(FeePreset::IbkrTiered)
+        };
+
+        assert!((fees.order_cost(1, 0.03) - 0.45).abs() < 1e-9);
+        assert!((fees.order_cost(1, 0.07) - 0.70).abs() < 1e-9);
+        assert!((fees.order_cost(1, 1.00) - 0.85).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_round_trip_includes_spread() {
+        let fees = FeeSchedule {
+            commission_per_contract: 0.50,
+            ..FeeSchedule::from_preset(FeePreset::Custom)
+        };
+
+        assert!((fees.round_trip_cost(1.00, 0.95, 1.05, 100.0) - 11.0).abs() < 1e-9);
+
+        let no_spread = FeeSchedule {
+            include_spread: false,
+            ..fees
+        };
+        assert!((no_spread.round_trip_cost(1.00, 0.95, 1.05, 100.0) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_preset() {
+        assert_eq!("tiered".parse::<FeePreset>(), Ok(FeePreset::IbkrTiered));
+        assert_eq!("IBKR_FIXED".parse::<FeePreset>(), Ok(FeePreset::IbkrFixed));
+        assert!("maker_taker".parse::<FeePreset>().is_err());
+    }
+}
@@ -1,7 +1,11 @@
+pub mod expression;
+pub mod fee_schedule;
 pub mod option_chain;
-pub mod stock;
 pub mod scanner_config;
+pub mod watchlist;
 
+pub use expression::*;
+pub use fee_schedule::*;
 pub use option_chain::*;
-pub use stock::*;
 pub use scanner_config::*;
+pub use watchlist::*;
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptionChain {
@@ -8,15 +9,302 @@ pub struct OptionChain {
     pub option_type: OptionType,
     pub expiration: DateTime<Utc>,
     pub strike: f64,
+    pub multiplier: f64,
     pub option_price: f64,
+    pub bid: f64,
+    pub ask: f64,
+    pub last: f64,
+    pub mark: f64,
     pub stock_price: f64,
     pub profit_percent: f64,
+    pub trading_cost: f64, // per contract, round trip including spread
     pub volume: i32,
+    pub open_interest: i32,
     pub implied_volatility: f64,
+    pub iv_status: IvStatus,
+    pub theoretical_value: f64,
+    pub early_exercise_premium: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub theta: f64,
+    pub vega: f64,
+    pub rho: f64,
+    pub prob_itm: f64,
+    pub prob_profit: f64,
+    pub prob_touch: f64,
+    pub expected_value: f64, // per contract
+    pub score: Option<f64>, // multi-factor rank score, set when ranking is enabled
+    pub metrics: OptionMetrics,
     pub timestamp: DateTime<Utc>,
     pub contract_id: String,
 }
 
+impl OptionChain {
+    // Bid/ask midpoint when both sides are quoted, otherwise the mark
+    pub fn mid_price(&self) -> f64 {
+        if self.bid > 0.0 && self.ask > 0.0 {
+            (self.bid + self.ask) / 2.0
+        } else {
+            self.mark
+        }
+    }
+    
+    // Premium per share with commissions, fees and spread cost folded in
+    pub fn cost_basis(&self) -> f64 {
+        if self.multiplier > 0.0 {
+            self.option_price + self.trading_cost / self.multiplier
+        } else {
+            self.option_price
+        }
+    }
+    
+    // Premium per share kept after selling, net of commissions, fees and spread cost
+    pub fn short_proceeds(&self) -> f64 {
+        if self.multiplier > 0.0 {
+            self.option_price - self.trading_cost / self.multiplier
+        } else {
+            self.option_price
+        }
+    }
+    
+    // Percent out of the money relative to spot; negative when in the money
+    pub fn moneyness(&self) -> f64 {
+        if self.stock_price <= 0.0 {
+            return 0.0;
+        }
+        
+        match self.option_type {
+            OptionType::Call => (self.strike - self.stock_price) / self.stock_price * 100.0,
+            OptionType::Put => (self.stock_price - self.strike) / self.stock_price * 100.0,
+        }
+    }
+    
+    // Bid/ask width as a percent of the midpoint, None without a two-sided quote
+    pub fn spread_percent(&self) -> Option<f64> {
+        if self.bid > 0.0 && self.ask >= self.bid {
+            Some((self.ask - self.bid) / ((self.bid + self.ask) / 2.0) * 100.0)
+        } else {
+            None
+        }
+    }
+}
+
+// An unquoted, unpriced standard (100 share) call
+impl Default for OptionChain {
+    fn default() -> Self {
+        Self {
+            symbol: String::new(),
+            option_type: OptionType::Call,
+            expiration: DateTime::<Utc>::default(),
+            strike: 0.0,
+            multiplier: 100.0,
+            option_price: 0.0,
+            bid: 0.0,
+            ask: 0.0,
+            last: 0.0,
+            mark: 0.0,
+            stock_price: 0.0,
+            profit_percent: 0.0,
+            trading_cost: 0.0,
+            volume: 0,
+            open_interest: 0,
+            implied_volatility: 0.0,
+            iv_status: IvStatus::Unknown,
+            theoretical_value: 0.0,
+            early_exercise_premium: 0.0,
+            delta: 0.0,
+            gamma: 0.0,
+            theta: 0.0,
+            vega: 0.0,
+            rho: 0.0,
+            prob_itm: 0.0,
+            prob_profit: 0.0,
+            prob_touch: 0.0,
+            expected_value: 0.0,
+            score: None,
+            metrics: OptionMetrics::default(),
+            timestamp: Utc::now(),
+            contract_id: String::new(),
+        }
+    }
+}
+
+// Test fixture: a contract on a $100 stock marked at `option_price` with a solved 30% IV,
+// expiring at a fixed date. Tests override only the fields they exercise.
+#[cfg(test)]
+impl OptionChain {
+    pub(crate) fn sample(option_type: OptionType, strike: f64, option_price: f64) -> Self {
+        let chain = Self {
+            symbol: "AAPL".to_string(),
+            option_type,
+            expiration: DateTime::from_timestamp(1_750_000_000, 0).unwrap(),
+            strike,
+            option_price,
+            mark: option_price,
+            stock_price: 100.0,
+            implied_volatility: 0.3,
+            iv_status: IvStatus::Solved,
+            ..Self::default()
+        };
+        chain.with_sample_id()
+    }
+
+    // Expiry, right and strike (e.g. "20250615C100"), so calls, puts and expiries never share
+    // an id. Call it again after a fixture moves the expiry or flips the right.
+    pub(crate) fn with_sample_id(mut self) -> Self {
+        let right = match self.option_type {
+            OptionType::Call => "C",
+            OptionType::Put => "P",
+        };
+        self.contract_id = format!("{}{}{}", self.expiration.format("%Y%m%d"), right, self.strike);
+        self
+    }
+}
+
+// Per-contract outcome of buying the option at `option_price`, in dollars unless noted
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct OptionMetrics {
+    pub breakeven: f64,
+    pub max_gain: Option<f64>, // None when unlimited (long calls)
+    pub max_loss: f64,
+    pub return_on_capital: f64, // percent, if the stock moves one sigma (at the IV) its way
+    pub annualized_return: f64, // percent, losses not annualized
+    pub days_to_expiry: f64,
+}
+
+// Column the results table is ordered by, best first
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    #[default]
+    Profit,
+    Breakeven,
+    MaxGain,
+    MaxLoss,
+    ReturnOnCapital,
+    Annualized,
+    DaysToExpiry,
+    ProbItm,
+    ProbTouch,
+    ExpectedValue,
+}
+
+impl SortKey {
+    pub const NAMES: [&'static str; 10] = [
+        "profit",
+        "breakeven",
+        "max_gain",
+        "max_loss",
+        "return",
+        "annualized",
+        "dte",
+        "prob_itm",
+        "prob_touch",
+        "ev",
+    ];
+
+    fn value(&self, option: &OptionChain) -> f64 {
+        let metrics = &option.metrics;
+        match self {
+            SortKey::Profit => option.profit_percent,
+            SortKey::Breakeven => metrics.breakeven,
+            SortKey::MaxGain => metrics.max_gain.unwrap_or(f64::INFINITY),
+            SortKey::MaxLoss => metrics.max_loss,
+            SortKey::ReturnOnCapital => metrics.return_on_capital,
+            SortKey::Annualized => metrics.annualized_return,
+            SortKey::DaysToExpiry => metrics.days_to_expiry,
+            SortKey::ProbItm => option.prob_itm,
+            SortKey::ProbTouch => option.prob_touch,
+            SortKey::ExpectedValue => option.expected_value,
+        }
+    }
+
+    // Smaller is better for risk and time, larger for everything else
+    fn ascending(&self) -> bool {
+        matches!(self, SortKey::MaxLoss | SortKey::DaysToExpiry | SortKey::Breakeven)
+    }
+
+    pub fn sort(&self, options: &mut [OptionChain]) {
+        options.sort_by(|a, b| {
+            let ordering = self.value(a).total_cmp(&self.value(b));
+            if self.ascending() {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SortKey::Profit => "profit",
+            SortKey::Breakeven => "breakeven",
+            SortKey::MaxGain => "max_gain",
+            SortKey::MaxLoss => "max_loss",
+            SortKey::ReturnOnCapital => "return",
+            SortKey::Annualized => "annualized",
+            SortKey::DaysToExpiry => "dte",
+            SortKey::ProbItm => "prob_itm",
+            SortKey::ProbTouch => "prob_touch",
+            SortKey::ExpectedValue => "ev",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "profit" => Ok(SortKey::Profit),
+            "breakeven" => Ok(SortKey::Breakeven),
+            "max_gain" => Ok(SortKey::MaxGain),
+            "max_loss" => Ok(SortKey::MaxLoss),
+            "return" | "return_on_capital" | "roc" => Ok(SortKey::ReturnOnCapital),
+            "annualized" => Ok(SortKey::Annualized),
+            "dte" | "days_to_expiry" => Ok(SortKey::DaysToExpiry),
+            "prob_itm" => Ok(SortKey::ProbItm),
+            "prob_touch" => Ok(SortKey::ProbTouch),
+            "ev" | "expected_value" => Ok(SortKey::ExpectedValue),
+            other => Err(format!(
+                "unknown sort key '{}', expected one of: {}",
+                other,
+                SortKey::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+// Where `implied_volatility` came from
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum IvStatus {
+    #[default]
+    Unknown,
+    Solved,
+    GatewayFallback,
+    Failed,
+}
+
+impl IvStatus {
+    pub fn is_usable(&self) -> bool {
+        matches!(self, IvStatus::Solved | IvStatus::GatewayFallback)
+    }
+}
+
+impl fmt::Display for IvStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IvStatus::Unknown => write!(f, "unknown"),
+            IvStatus::Solved => write!(f, "solved"),
+            IvStatus::GatewayFallback => write!(f, "gateway"),
+            IvStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum OptionType {
     Call,
@@ -41,19 +329,197 @@ impl OptionType {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Contract-level gates the scanner applies before single-leg strategies see the chain;
+// multi-leg strategies opt out so they keep their wings. Unset bounds don't filter; deltas are
+// absolute, moneyness is percent out of the money (negative when in the money).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct OptionChainFilters {
-    pub min_profit_percent: f64,
-    pub min_volume: i32,
-    pub max_days_to_expiry: i32,
+    pub min_volume: Option<i32>,
+    pub min_open_interest: Option<i32>,
+    pub min_days_to_expiry: Option<f64>,
+    pub max_days_to_expiry: Option<f64>,
+    pub min_moneyness: Option<f64>,
+    pub max_moneyness: Option<f64>,
+    pub min_delta: Option<f64>,
+    pub max_delta: Option<f64>,
+    pub max_spread_percent: Option<f64>,
+    pub min_premium: Option<f64>,
 }
 
-impl Default for OptionChainFilters {
-    fn default() -> Self {
-        Self {
-            min_profit_percent: 2.0,
-            min_volume: 100,
-            max_days_to_expiry: 45,
+impl OptionChainFilters {
+    // Per-symbol values win over these
+    pub fn with_overrides(&self, overrides: &OptionChainFilters) -> OptionChainFilters {
+        OptionChainFilters {
+            min_volume: overrides.min_volume.or(self.min_volume),
+            min_open_interest: overrides.min_open_interest.or(self.min_open_interest),
+            min_days_to_expiry: overrides.min_days_to_expiry.or(self.min_days_to_expiry),
+            max_days_to_expiry: overrides.max_days_to_expiry.or(self.max_days_to_expiry),
+            min_moneyness: overrides.min_moneyness.or(self.min_moneyness),
+            max_moneyness: overrides.max_moneyness.or(self.max_moneyness),
+            min_delta: overrides.min_delta.or(self.min_delta),
+            max_delta: overrides.max_delta.or(self.max_delta),
+            max_spread_percent: overrides.max_spread_percent.or(self.max_spread_percent),
+            min_premium: overrides.min_premium.or(self.min_premium),
         }
     }
+    
+    // Name of the first filter the contract fails, if any
+    pub fn rejection(&self, chain: &OptionChain) -> Option<&'static str> {
+        let outside = |value: f64, min: Option<f64>, max: Option<f64>| {
+            min.is_some_and(|min| value < min) || max.is_some_and(|max| value > max)
+        };
+        
+        if self.min_volume.is_some_and(|min| chain.volume < min) {
+            Some("volume")
+        } else if self.min_open_interest.is_some_and(|min| chain.open_interest < min) {
+            Some("open interest")
+        } else if outside(
+            chain.metrics.days_to_expiry,
+            self.min_days_to_expiry,
+            self.max_days_to_expiry,
+        ) {
+            Some("days to expiry")
+        } else if outside(chain.moneyness(), self.min_moneyness, self.max_moneyness) {
+            Some("moneyness")
+        } else if outside(chain.delta.abs(), self.min_delta, self.max_delta) {
+            Some("delta")
+        } else if self
+            .max_spread_percent
+            .is_some_and(|max| chain.spread_percent().is_none_or(|spread| spread > max))
+        {
+            Some("bid/ask spread")
+        } else if outside(chain.option_price, self.min_premium, None) {
+            Some("premium")
+        } else {
+            None
+        }
+    }
+    
+    pub fn matches(&self, chain: &OptionChain) -> bool {
+        self.rejection(chain).is_none()
+    }
+    
+    // Rejects inverted ranges so a typo doesn't silently filter out everything
+    pub fn validate(&self) -> Result<(), String> {
+        let ranges = [
+            ("days to expiry", self.min_days_to_expiry, self.max_days_to_expiry),
+            ("moneyness", self.min_moneyness, self.max_moneyness),
+            ("delta", self.min_delta, self.max_delta),
+        ];
+        
+        for (name, min, max) in ranges {
+            if let (Some(min), Some(max)) = (min, max) {
+                if min > max {
+                    return Err(format!("{} minimum {} is above maximum {}", name, min, max));
+                }
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(strike: f64, max_gain: Option<f64>, max_loss: f64) -> OptionChain {
+        OptionChain {
+            metrics: OptionMetrics {
+                max_gain,
+                max_loss,
+                ..OptionMetrics::default()
+            },
+            ..OptionChain::sample(OptionType::Put, strike, max_loss / 100.0)
+        }
+    }
+
+    #[test]
+    fn test_sort_key_orders_best_first() {
+        let mut options = vec![
+            option(90.0, Some(8_000.0), 300.0),
+            option(95.0, None, 500.0),
+            option(100.0, Some(9_000.0), 100.0),
+        ];
+
+        SortKey::MaxGain.sort(&mut options);
+        let strikes: Vec<f64> = options.iter().map(|o| o.strike).collect();
+        assert_eq!(strikes, vec![95.0, 100.0, 90.0]);
+
+        SortKey::MaxLoss.sort(&mut options);
+        let strikes: Vec<f64> = options.iter().map(|o| o.strike).collect();
+        assert_eq!(strikes, vec![100.0, 90.0, 95.0]);
+    }
+
+    #[test]
+    fn test_cost_basis_spreads_trading_cost_per_share() {
+        let mut put = option(100.0, None, 250.0);
+        put.trading_cost = 12.0;
+
+        assert!((put.cost_basis() - 2.62).abs() < 1e-9);
+        assert!((put.short_proceeds() - 2.38).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sort_key_round_trips_through_names() {
+        for name in SortKey::NAMES {
+            assert_eq!(name.parse::<SortKey>().unwrap().to_string(), name);
+        }
+        assert!("volume".parse::<SortKey>().is_err());
+    }
+
+    #[test]
+    fn test_filters_report_first_rejection() {
+        let mut put = option(95.0, None, 250.0);
+        put.volume = 50;
+        put.open_interest = 200;
+        put.delta = -0.3;
+        put.bid = 1.9;
+        put.ask = 2.1;
+        put.metrics.days_to_expiry = 30.0;
+
+        assert!((put.moneyness() - 5.0).abs() < 1e-9);
+        assert!((put.spread_percent().unwrap() - 10.0).abs() < 1e-9);
+
+        let filters = OptionChainFilters {
+            min_open_interest: Some(100),
+            max_days_to_expiry: Some(45.0),
+            min_moneyness: Some(0.0),
+            max_delta: Some(0.35),
+            max_spread_percent: Some(15.0),
+            min_premium: Some(0.5),
+            ..OptionChainFilters::default()
+        };
+        assert!(filters.matches(&put));
+
+        let strict = OptionChainFilters {
+            min_volume: Some(100),
+            ..filters.clone()
+        };
+        assert_eq!(strict.rejection(&put), Some("volume"));
+
+        // Per-symbol values replace the global ones, unset ones fall through
+        let overrides = OptionChainFilters {
+            max_spread_percent: Some(5.0),
+            ..OptionChainFilters::default()
+        };
+        let merged = filters.with_overrides(&overrides);
+        assert_eq!(merged.rejection(&put), Some("bid/ask spread"));
+        assert_eq!(merged.min_open_interest, Some(100));
+
+        put.bid = 0.0;
+        assert_eq!(filters.rejection(&put), Some("bid/ask spread"));
+    }
+
+    #[test]
+    fn test_filters_reject_inverted_ranges() {
+        let filters = OptionChainFilters {
+            min_delta: Some(0.4),
+            max_delta: Some(0.2),
+            ..OptionChainFilters::default()
+        };
+
+        assert!(filters.validate().unwrap_err().contains("delta"));
+        assert!(OptionChainFilters::default().validate().is_ok());
+    }
 }
@@ -1,5 +1,10 @@
+use crate::models::{
+    ExpressionError, FeePreset, FeeSchedule, FilterExpression, OptionChainFilters,
+    SortExpression, SortKey,
+};
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScannerConfig {
@@ -7,13 +12,43 @@ pub struct ScannerConfig {
     pub ibkr_gateway_port: u16,
     pub ibkr_gateway_ssl: bool,
     pub min_profit_percent: f64,
+    pub min_prob_itm: f64,
+    pub min_prob_touch: f64,
+    pub min_expected_value: Option<f64>,
+    pub sort_by: SortKey,
+    pub filter_expression: Option<FilterExpression>,
+    pub sort_expression: Option<SortExpression>,
+    pub strategies: Vec<String>,
+    pub filters: OptionChainFilters,
+    pub fees: FeeSchedule,
+    pub covered_calls: CoveredCallConfig,
+    pub cash_secured_puts: CashSecuredPutConfig,
+    pub vertical_spreads: VerticalSpreadConfig,
+    pub neutral_strategies: NeutralStrategyConfig,
+    pub calendar_spreads: CalendarSpreadConfig,
+    pub ranking: RankingConfig,
+    pub cycle_diff: CycleDiffConfig,
+    pub risk_free_rate: f64,
+    pub dividend_yield: f64,
+    pub tree_steps: usize,
     pub scan_interval_seconds: u64,
+    pub tickle_interval_seconds: u64,
     pub max_retry_attempts: u32,
+    pub retry_base_delay_ms: u64,
+    pub retry_max_delay_ms: u64,
     pub request_timeout_seconds: u64,
+    pub rate_limit_per_second: f64,
+    pub endpoint_rate_limits: Vec<EndpointRateLimit>,
+    pub max_concurrent_symbols: usize,
+    pub strike_count: usize,
+    pub option_months: usize,
     pub nasdaq_stocks: Vec<String>,
+    pub watchlist: Option<String>,
+    pub watchlist_dir: String,
     pub ibkr_account_id: String,
     pub log_file_path: String,
     pub options_log_dir: String,
+    pub conid_cache_path: String,
 }
 
 impl ScannerConfig {
@@ -33,29 +68,229 @@ impl ScannerConfig {
                 .unwrap_or_else(|_| "2.0".to_string())
                 .parse()
                 .map_err(|_| ConfigError::InvalidFloat)?,
+            min_prob_itm: env::var("MIN_PROB_ITM")
+                .unwrap_or_else(|_| "0.0".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidFloat)?,
+            min_prob_touch: env::var("MIN_PROB_TOUCH")
+                .unwrap_or_else(|_| "0.0".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidFloat)?,
+            min_expected_value: optional_float("MIN_EXPECTED_VALUE")?,
+            sort_by: match env::var("SORT_BY") {
+                Ok(value) if !value.trim().is_empty() => {
+                    value.parse().map_err(ConfigError::InvalidSortKey)?
+                }
+                _ => SortKey::default(),
+            },
+            filter_expression: optional_expression("FILTER_EXPR")?,
+            sort_expression: optional_expression("SORT_EXPR")?,
+            strategies: env::var("STRATEGIES")
+                .unwrap_or_else(|_| "long_options".to_string())
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            filters: filters_from_env()?,
+            fees: fee_schedule_from_env()?,
+            covered_calls: CoveredCallConfig {
+                min_static_return: env::var("CC_MIN_STATIC_RETURN")
+                    .unwrap_or_else(|_| "1.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                min_if_called_return: env::var("CC_MIN_IF_CALLED_RETURN")
+                    .unwrap_or_else(|_| "0.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                min_downside_protection: env::var("CC_MIN_DOWNSIDE_PROTECTION")
+                    .unwrap_or_else(|_| "0.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                min_annualized_yield: env::var("CC_MIN_ANNUALIZED_YIELD")
+                    .unwrap_or_else(|_| "10.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+            },
+            cash_secured_puts: CashSecuredPutConfig {
+                min_yield: env::var("CSP_MIN_YIELD")
+                    .unwrap_or_else(|_| "1.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                max_delta: env::var("CSP_MAX_DELTA")
+                    .unwrap_or_else(|_| "0.30".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+            },
+            vertical_spreads: VerticalSpreadConfig {
+                max_width: env::var("SPREAD_MAX_WIDTH")
+                    .unwrap_or_else(|_| "10.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                min_credit: env::var("SPREAD_MIN_CREDIT")
+                    .unwrap_or_else(|_| "0.20".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                top_per_symbol: env::var("SPREAD_TOP_PER_SYMBOL")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidUint)?,
+            },
+            neutral_strategies: NeutralStrategyConfig {
+                wing_width: env::var("CONDOR_WING_WIDTH")
+                    .unwrap_or_else(|_| "5.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                min_move_ratio: env::var("MIN_MOVE_RATIO")
+                    .unwrap_or_else(|_| "0.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+            },
+            calendar_spreads: CalendarSpreadConfig {
+                min_iv_differential: env::var("CALENDAR_MIN_IV_DIFF")
+                    .unwrap_or_else(|_| "0.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                include_diagonals: env::var("CALENDAR_DIAGONALS")
+                    .unwrap_or_else(|_| "true".to_string())
+                    .parse()
+                    .unwrap_or(true),
+                top_per_symbol: env::var("CALENDAR_TOP_PER_SYMBOL")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidUint)?,
+            },
+            ranking: RankingConfig {
+                enabled: env::var("RANKING_ENABLED")
+                    .unwrap_or_else(|_| "false".to_string())
+                    .parse()
+                    .unwrap_or(false),
+                return_weight: env::var("RANK_RETURN_WEIGHT")
+                    .unwrap_or_else(|_| "0.4".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                probability_weight: env::var("RANK_PROBABILITY_WEIGHT")
+                    .unwrap_or_else(|_| "0.3".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                liquidity_weight: env::var("RANK_LIQUIDITY_WEIGHT")
+                    .unwrap_or_else(|_| "0.2".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                iv_rank_weight: env::var("RANK_IV_WEIGHT")
+                    .unwrap_or_else(|_| "-0.1".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                top_n: env::var("RANK_TOP_N")
+                    .unwrap_or_else(|_| "0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidUint)?,
+                top_per_symbol: env::var("RANK_TOP_PER_SYMBOL")
+                    .unwrap_or_else(|_| "0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidUint)?,
+            },
+            cycle_diff: CycleDiffConfig {
+                enabled: env::var("CYCLE_DIFF_ENABLED")
+                    .unwrap_or_else(|_| "false".to_string())
+                    .parse()
+                    .unwrap_or(false),
+                min_price_change_percent: env::var("DIFF_MIN_PRICE_CHANGE")
+                    .unwrap_or_else(|_| "5.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                min_profit_change: env::var("DIFF_MIN_PROFIT_CHANGE")
+                    .unwrap_or_else(|_| "1.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+                min_iv_change: env::var("DIFF_MIN_IV_CHANGE")
+                    .unwrap_or_else(|_| "2.0".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+            },
+            risk_free_rate: env::var("RISK_FREE_RATE")
+                .unwrap_or_else(|_| "0.045".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidFloat)?,
+            dividend_yield: env::var("DIVIDEND_YIELD")
+                .unwrap_or_else(|_| "0.0".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidFloat)?,
+            tree_steps: env::var("TREE_STEPS")
+                .unwrap_or_else(|_| "200".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidUint)?,
             scan_interval_seconds: env::var("SCAN_INTERVAL_SECONDS")
                 .unwrap_or_else(|_| "300".to_string())
                 .parse()
                 .map_err(|_| ConfigError::InvalidUint)?,
+            tickle_interval_seconds: tickle_interval(
+                "TICKLE_INTERVAL_SECONDS",
+                env::var("TICKLE_INTERVAL_SECONDS")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidUint)?,
+            )?,
             max_retry_attempts: env::var("MAX_RETRY_ATTEMPTS")
                 .unwrap_or_else(|_| "3".to_string())
                 .parse()
                 .map_err(|_| ConfigError::InvalidUint)?,
+            retry_base_delay_ms: env::var("RETRY_BASE_DELAY_MS")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidUint)?,
+            retry_max_delay_ms: env::var("RETRY_MAX_DELAY_MS")
+                .unwrap_or_else(|_| "10000".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidUint)?,
             request_timeout_seconds: env::var("REQUEST_TIMEOUT_SECONDS")
                 .unwrap_or_else(|_| "30".to_string())
                 .parse()
                 .map_err(|_| ConfigError::InvalidUint)?,
+            rate_limit_per_second: positive_rate(
+                "RATE_LIMIT_PER_SECOND",
+                env::var("RATE_LIMIT_PER_SECOND")
+                    .unwrap_or_else(|_| "10".to_string())
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidFloat)?,
+            )?,
+            endpoint_rate_limits: match env::var("ENDPOINT_RATE_LIMITS") {
+                Ok(value) => EndpointRateLimit::parse_list(&value)?,
+                Err(_) => EndpointRateLimit::defaults(),
+            },
+            max_concurrent_symbols: env::var("MAX_CONCURRENT_SYMBOLS")
+                .unwrap_or_else(|_| "4".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidUint)?,
+            strike_count: env::var("STRIKE_COUNT")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidUint)?,
+            option_months: env::var("OPTION_MONTHS")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .map_err(|_| ConfigError::InvalidUint)?,
+            // Empty counts as unset, so the .env.example placeholder scans the defaults
             nasdaq_stocks: env::var("NASDAQ_STOCKS")
-                .unwrap_or_else(|_| "AAPL,MSFT,GOOGL".to_string())
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .unwrap_or_else(|| "AAPL,MSFT,GOOGL".to_string())
                 .split(',')
                 .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
                 .collect(),
+            watchlist: env::var("WATCHLIST")
+                .ok()
+                .filter(|value| !value.trim().is_empty()),
+            watchlist_dir: env::var("WATCHLIST_DIR")
+                .unwrap_or_else(|_| "./stocks".to_string()),
             ibkr_account_id: env::var("IBKR_ACCOUNT_ID")
                 .unwrap_or_else(|_| "".to_string()),
             log_file_path: env::var("LOG_FILE_PATH")
                 .unwrap_or_else(|_| "./logs/scanner.log".to_string()),
             options_log_dir: env::var("OPTIONS_LOG_DIR")
                 .unwrap_or_else(|_| "./logs/options/".to_string()),
+            conid_cache_path: env::var("CONID_CACHE_PATH")
+                .unwrap_or_else(|_| "./cache/conids.json".to_string()),
         })
     }
     
@@ -65,6 +300,295 @@ impl ScannerConfig {
     }
 }
 
+// Thresholds for the covered-call scan, all in percent
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoveredCallConfig {
+    pub min_static_return: f64,
+    pub min_if_called_return: f64,
+    pub min_downside_protection: f64,
+    pub min_annualized_yield: f64,
+}
+
+impl Default for CoveredCallConfig {
+    fn default() -> Self {
+        Self {
+            min_static_return: 1.0,
+            min_if_called_return: 0.0,
+            min_downside_protection: 0.0,
+            min_annualized_yield: 10.0,
+        }
+    }
+}
+
+// Thresholds for the cash-secured put scan: yield on collateral in percent, delta as an
+// absolute value
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CashSecuredPutConfig {
+    pub min_yield: f64,
+    pub max_delta: f64,
+}
+
+impl Default for CashSecuredPutConfig {
+    fn default() -> Self {
+        Self {
+            min_yield: 1.0,
+            max_delta: 0.30,
+        }
+    }
+}
+
+// Vertical spread limits; width and credit are per share and can be overridden per symbol
+// from the watchlist
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VerticalSpreadConfig {
+    pub max_width: f64,
+    pub min_credit: f64,
+    pub top_per_symbol: usize,
+}
+
+impl Default for VerticalSpreadConfig {
+    fn default() -> Self {
+        Self {
+            max_width: 10.0,
+            min_credit: 0.20,
+            top_per_symbol: 5,
+        }
+    }
+}
+
+// Iron condor, short strangle and long straddle evaluation. `min_move_ratio` is compared
+// against each setup's breakeven (or debit) relative to the ATM-IV expected move
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NeutralStrategyConfig {
+    pub wing_width: f64,
+    pub min_move_ratio: f64,
+}
+
+impl Default for NeutralStrategyConfig {
+    fn default() -> Self {
+        Self {
+            wing_width: 5.0,
+            min_move_ratio: 0.0,
+        }
+    }
+}
+
+// Calendar and diagonal spreads; the IV differential is front minus back month in vol points
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CalendarSpreadConfig {
+    pub min_iv_differential: f64,
+    pub include_diagonals: bool,
+    pub top_per_symbol: usize,
+}
+
+impl Default for CalendarSpreadConfig {
+    fn default() -> Self {
+        Self {
+            min_iv_differential: 0.0,
+            include_diagonals: true,
+            top_per_symbol: 5,
+        }
+    }
+}
+
+// Weighted score over min-max normalized factors; a negative IV rank weight favours contracts
+// whose IV is low for their symbol. Limits of 0 keep everything.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RankingConfig {
+    pub enabled: bool,
+    pub return_weight: f64,
+    pub probability_weight: f64,
+    pub liquidity_weight: f64,
+    pub iv_rank_weight: f64,
+    pub top_n: usize,
+    pub top_per_symbol: usize,
+}
+
+impl Default for RankingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            return_weight: 0.4,
+            probability_weight: 0.3,
+            liquidity_weight: 0.2,
+            iv_rank_weight: -0.1,
+            top_n: 0,
+            top_per_symbol: 0,
+        }
+    }
+}
+
+// What counts as a material change between cycles: price in percent of the previous price,
+// profit in percentage points, IV in vol points
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CycleDiffConfig {
+    pub enabled: bool,
+    pub min_price_change_percent: f64,
+    pub min_profit_change: f64,
+    pub min_iv_change: f64,
+}
+
+impl Default for CycleDiffConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_price_change_percent: 5.0,
+            min_profit_change: 1.0,
+            min_iv_change: 2.0,
+        }
+    }
+}
+
+// Unset or empty means "not configured"
+fn optional_float(name: &str) -> Result<Option<f64>, ConfigError> {
+    env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| value.trim().parse())
+        .transpose()
+        .map_err(|_| ConfigError::InvalidFloat)
+}
+
+fn optional_expression<T>(name: &str) -> Result<Option<T>, ConfigError>
+where
+    T: FromStr<Err = ExpressionError>,
+{
+    env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| value.parse())
+        .transpose()
+        .map_err(|e| ConfigError::InvalidExpression(format!("{}: {}", name, e)))
+}
+
+fn optional_int(name: &str) -> Result<Option<i32>, ConfigError> {
+    env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(|value| value.trim().parse())
+        .transpose()
+        .map_err(|_| ConfigError::InvalidUint)
+}
+
+fn filters_from_env() -> Result<OptionChainFilters, ConfigError> {
+    let filters = OptionChainFilters {
+        min_volume: optional_int("MIN_VOLUME")?,
+        min_open_interest: optional_int("MIN_OPEN_INTEREST")?,
+        min_days_to_expiry: optional_float("MIN_DTE")?,
+        max_days_to_expiry: optional_float("MAX_DTE")?,
+        min_moneyness: optional_float("MIN_MONEYNESS")?,
+        max_moneyness: optional_float("MAX_MONEYNESS")?,
+        min_delta: optional_float("MIN_DELTA")?,
+        max_delta: optional_float("MAX_DELTA")?,
+        max_spread_percent: optional_float("MAX_SPREAD_PERCENT")?,
+        min_premium: optional_float("MIN_PREMIUM")?,
+    };
+    
+    filters.validate().map_err(ConfigError::InvalidFilter)?;
+    Ok(filters)
+}
+
+// FEE_SCHEDULE picks the preset; the individual fee variables override its values
+fn fee_schedule_from_env() -> Result<FeeSchedule, ConfigError> {
+    let preset: FeePreset = match env::var("FEE_SCHEDULE") {
+        Ok(value) if !value.trim().is_empty() => {
+            value.parse().map_err(ConfigError::InvalidFeeSchedule)?
+        }
+        _ => FeePreset::default(),
+    };
+    
+    let mut fees = FeeSchedule::from_preset(preset);
+    
+    if let Some(value) = optional_float("COMMISSION_PER_CONTRACT")? {
+        fees.commission_per_contract = value;
+    }
+    if let Some(value) = optional_float("COMMISSION_MIN_PER_ORDER")? {
+        fees.min_per_order = value;
+    }
+    if let Some(value) = optional_float("EXCHANGE_FEE_PER_CONTRACT")? {
+        fees.exchange_fee_per_contract = value;
+    }
+    if let Some(value) = optional_float("REGULATORY_FEE_PER_CONTRACT")? {
+        fees.regulatory_fee_per_contract = value;
+    }
+    if let Ok(value) = env::var("INCLUDE_SPREAD_COST") {
+        fees.include_spread = value.parse().unwrap_or(true);
+    }
+    
+    Ok(fees)
+}
+
+// A zero interval would tickle the gateway in a tight loop
+pub fn tickle_interval(name: &str, seconds: u64) -> Result<u64, ConfigError> {
+    if seconds >= 1 {
+        Ok(seconds)
+    } else {
+        Err(ConfigError::InvalidInterval(format!("{} must be at least 1 second", name)))
+    }
+}
+
+// A zero or negative budget would stall every request behind it
+pub fn positive_rate(name: &str, requests_per_second: f64) -> Result<f64, ConfigError> {
+    if requests_per_second.is_finite() && requests_per_second > 0.0 {
+        Ok(requests_per_second)
+    } else {
+        Err(ConfigError::InvalidRateLimit(format!(
+            "{} must be above 0 requests per second",
+            name
+        )))
+    }
+}
+
+// Per-endpoint request budget, e.g. "/iserver/marketdata/snapshot=5"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EndpointRateLimit {
+    pub path_prefix: String,
+    pub requests_per_second: f64,
+}
+
+impl EndpointRateLimit {
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self {
+                path_prefix: "/iserver/marketdata/snapshot".to_string(),
+                requests_per_second: 5.0,
+            },
+            Self {
+                path_prefix: "/iserver/secdef".to_string(),
+                requests_per_second: 5.0,
+            },
+            Self {
+                path_prefix: "/tickle".to_string(),
+                requests_per_second: 1.0,
+            },
+        ]
+    }
+    
+    // Comma-separated "prefix=rate" pairs
+    pub fn parse_list(value: &str) -> Result<Vec<Self>, ConfigError> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (prefix, rate) = entry
+                    .split_once('=')
+                    .ok_or_else(|| ConfigError::InvalidRateLimit(entry.to_string()))?;
+                
+                let requests_per_second: f64 = rate
+                    .trim()
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidRateLimit(entry.to_string()))?;
+                
+                Ok(Self {
+                    path_prefix: prefix.trim().to_string(),
+                    requests_per_second: positive_rate(entry, requests_per_second)?,
+                })
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
     #[error("Invalid port number")]
@@ -73,6 +597,50 @@ pub enum ConfigError {
     InvalidFloat,
     #[error("Invalid unsigned integer")]
     InvalidUint,
+    #[error("Invalid interval: {0}")]
+    InvalidInterval(String),
+    #[error("Invalid rate limit: {0}")]
+    InvalidRateLimit(String),
+    #[error("Invalid sort key: {0}")]
+    InvalidSortKey(String),
+    #[error("Invalid fee schedule: {0}")]
+    InvalidFeeSchedule(String),
+    #[error("Invalid option filter: {0}")]
+    InvalidFilter(String),
+    #[error("Invalid expression {0}")]
+    InvalidExpression(String),
     #[error("Missing required environment variable: {0}")]
     MissingEnv(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limits_must_be_positive() {
+        assert_eq!(positive_rate("RATE_LIMIT_PER_SECOND", 2.5).unwrap(), 2.5);
+        assert!(positive_rate("RATE_LIMIT_PER_SECOND", 0.0).is_err());
+        assert!(positive_rate("RATE_LIMIT_PER_SECOND", -1.0).is_err());
+
+        let limits = EndpointRateLimit::parse_list("/iserver/secdef=2, /tickle=0.5").unwrap();
+        assert_eq!(limits.len(), 2);
+        assert_eq!(limits[1].requests_per_second, 0.5);
+
+        for invalid in ["/iserver/secdef=0", "/tickle=-1", "/tickle=NaN", "/tickle"] {
+            assert!(matches!(
+                EndpointRateLimit::parse_list(invalid),
+                Err(ConfigError::InvalidRateLimit(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_tickle_interval_must_be_at_least_one_second() {
+        assert_eq!(tickle_interval("TICKLE_INTERVAL_SECONDS", 1).unwrap(), 1);
+        assert!(matches!(
+            tickle_interval("TICKLE_INTERVAL_SECONDS", 0),
+            Err(ConfigError::InvalidInterval(_))
+        ));
+    }
+}
@@ -0,0 +1,464 @@
+use crate::models::{OptionChainFilters, OptionType};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const MAX_SYMBOL_LEN: usize = 10;
+
+#[derive(Debug, Error)]
+pub enum WatchlistError {
+    #[error("Watchlist '{0}' not found (looked for .txt and .csv)")]
+    NotFound(String),
+    #[error("Failed to read watchlist {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("CSV error in watchlist {name}: {source}")]
+    Csv { name: String, source: csv::Error },
+    #[error("Invalid watchlist {name}: {}", problems.join("; "))]
+    Invalid { name: String, problems: Vec<String> },
+}
+
+// A symbol plus optional per-symbol overrides of the global scan settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WatchlistEntry {
+    pub symbol: String,
+    pub min_profit_percent: Option<f64>,
+    pub strike_count: Option<usize>,
+    pub rights: Option<Vec<OptionType>>,
+    pub spread_width: Option<f64>,
+    pub min_credit: Option<f64>,
+    pub filters: OptionChainFilters,
+}
+
+impl WatchlistEntry {
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_uppercase(),
+            min_profit_percent: None,
+            strike_count: None,
+            rights: None,
+            spread_width: None,
+            min_credit: None,
+            filters: OptionChainFilters::default(),
+        }
+    }
+
+    pub fn allows(&self, option_type: &OptionType) -> bool {
+        self.rights
+            .as_ref()
+            .is_none_or(|rights| rights.contains(option_type))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Watchlist {
+    pub name: String,
+    pub entries: Vec<WatchlistEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CsvRow {
+    symbol: String,
+    #[serde(default)]
+    min_profit_percent: Option<f64>,
+    #[serde(default)]
+    strike_count: Option<usize>,
+    #[serde(default)]
+    rights: Option<String>,
+    #[serde(default)]
+    spread_width: Option<f64>,
+    #[serde(default)]
+    min_credit: Option<f64>,
+    #[serde(default)]
+    min_volume: Option<i32>,
+    #[serde(default)]
+    min_open_interest: Option<i32>,
+    #[serde(default)]
+    min_dte: Option<f64>,
+    #[serde(default)]
+    max_dte: Option<f64>,
+    #[serde(default)]
+    min_moneyness: Option<f64>,
+    #[serde(default)]
+    max_moneyness: Option<f64>,
+    #[serde(default)]
+    min_delta: Option<f64>,
+    #[serde(default)]
+    max_delta: Option<f64>,
+    #[serde(default)]
+    max_spread_percent: Option<f64>,
+    #[serde(default)]
+    min_premium: Option<f64>,
+}
+
+impl Watchlist {
+    // Blank entries (a trailing comma, "NASDAQ_STOCKS=") are skipped rather than rejected
+    pub fn from_symbols(name: &str, symbols: &[String]) -> Result<Self, WatchlistError> {
+        let entries = symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, symbol)| !symbol.trim().is_empty())
+            .map(|(index, symbol)| (index + 1, WatchlistEntry::new(symbol.trim())))
+            .collect();
+
+        Self::validated(name, entries)
+    }
+
+    // Loads one or more named lists ("nasdaq_stocks,dividends") from `dir`, or explicit paths
+    pub fn load_named(names: &str, dir: &str) -> Result<Self, WatchlistError> {
+        let mut merged = Watchlist {
+            name: names.to_string(),
+            entries: Vec::new(),
+        };
+        let mut seen = HashSet::new();
+
+        for name in names.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let list = Self::load(&Self::resolve_path(name, dir)?)?;
+
+            for entry in list.entries {
+                if seen.insert(entry.symbol.clone()) {
+                    merged.entries.push(entry);
+                } else {
+                    warn!("{} appears in more than one watchlist, keeping the first", entry.symbol);
+                }
+            }
+        }
+
+        Ok(merged)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, WatchlistError> {
+        let contents = fs::read_to_string(path).map_err(|source| WatchlistError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("csv") => Self::parse_csv(&name, &contents),
+            _ => Self::parse_text(&name, &contents),
+        }
+    }
+
+    // Available watchlist names in `dir`
+    pub fn available(dir: &str) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| {
+                        matches!(path.extension().and_then(|e| e.to_str()), Some("txt" | "csv"))
+                    })
+                    .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    // One symbol per line, '#' starts a comment
+    pub fn parse_text(name: &str, contents: &str) -> Result<Self, WatchlistError> {
+        let entries = contents
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let symbol = line.split('#').next().unwrap_or_default().trim();
+                (!symbol.is_empty()).then(|| (index + 1, WatchlistEntry::new(symbol)))
+            })
+            .collect();
+
+        Self::validated(name, entries)
+    }
+
+    // Columns: symbol[,min_profit_percent,strike_count,rights,spread_width,min_credit] with
+    // rights "C", "P" or "C;P", plus any of the filter columns (min_volume, min_open_interest,
+    // min_dte, max_dte, min_moneyness, max_moneyness, min_delta, max_delta,
+    // max_spread_percent, min_premium)
+    pub fn parse_csv(name: &str, contents: &str) -> Result<Self, WatchlistError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .comment(Some(b'#'))
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(contents.as_bytes());
+
+        let mut entries = Vec::new();
+        let mut problems = Vec::new();
+
+        for (index, row) in reader.deserialize::<CsvRow>().enumerate() {
+            let row = row.map_err(|source| WatchlistError::Csv {
+                name: name.to_string(),
+                source,
+            })?;
+            let line = index + 2; // header is line 1
+
+            let rights = match row.rights.as_deref().map(parse_rights) {
+                Some(Ok(rights)) => Some(rights),
+                Some(Err(value)) => {
+                    problems.push(format!("line {}: invalid rights '{}'", line, value));
+                    None
+                }
+                None => None,
+            };
+
+            entries.push((
+                line,
+                WatchlistEntry {
+                    symbol: row.symbol.to_uppercase(),
+                    min_profit_percent: row.min_profit_percent,
+                    strike_count: row.strike_count,
+                    rights,
+                    spread_width: row.spread_width,
+                    min_credit: row.min_credit,
+                    filters: OptionChainFilters {
+                        min_volume: row.min_volume,
+                        min_open_interest: row.min_open_interest,
+                        min_days_to_expiry: row.min_dte,
+                        max_days_to_expiry: row.max_dte,
+                        min_moneyness: row.min_moneyness,
+                        max_moneyness: row.max_moneyness,
+                        min_delta: row.min_delta,
+                        max_delta: row.max_delta,
+                        max_spread_percent: row.max_spread_percent,
+                        min_premium: row.min_premium,
+                    },
+                },
+            ));
+        }
+
+        if !problems.is_empty() {
+            return Err(WatchlistError::Invalid {
+                name: name.to_string(),
+                problems,
+            });
+        }
+
+        Self::validated(name, entries)
+    }
+
+    pub fn symbols(&self) -> Vec<String> {
+        self.entries.iter().map(|entry| entry.symbol.clone()).collect()
+    }
+
+    fn resolve_path(name: &str, dir: &str) -> Result<PathBuf, WatchlistError> {
+        let direct = PathBuf::from(name);
+        if direct.extension().is_some() && direct.is_file() {
+            return Ok(direct);
+        }
+
+        ["txt", "csv"]
+            .iter()
+            .map(|ext| Path::new(dir).join(format!("{}.{}", name, ext)))
+            .find(|path| path.is_file())
+            .ok_or_else(|| WatchlistError::NotFound(name.to_string()))
+    }
+
+    fn validated(name: &str, entries: Vec<(usize, WatchlistEntry)>) -> Result<Self, WatchlistError> {
+        let mut problems = Vec::new();
+        let mut seen: Vec<(String, usize)> = Vec::new();
+
+        for (line, entry) in &entries {
+            if !is_valid_ticker(&entry.symbol) {
+                problems.push(format!("line {}: invalid ticker '{}'", line, entry.symbol));
+            }
+
+            if let Some((_, first)) = seen.iter().find(|(symbol, _)| *symbol == entry.symbol) {
+                problems.push(format!(
+                    "line {}: duplicate {} (first on line {})",
+                    line, entry.symbol, first
+                ));
+            } else {
+                seen.push((entry.symbol.clone(), *line));
+            }
+
+            if entry.strike_count == Some(0) {
+                problems.push(format!("line {}: strike_count must be positive", line));
+            }
+
+            if entry.spread_width.is_some_and(|width| width <= 0.0) {
+                problems.push(format!("line {}: spread_width must be positive", line));
+            }
+
+            if let Err(problem) = entry.filters.validate() {
+                problems.push(format!("line {}: {}", line, problem));
+            }
+        }
+
+        if entries.is_empty() {
+            problems.push("no symbols".to_string());
+        }
+
+        if !problems.is_empty() {
+            return Err(WatchlistError::Invalid {
+                name: name.to_string(),
+                problems,
+            });
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            entries: entries.into_iter().map(|(_, entry)| entry).collect(),
+        })
+    }
+}
+
+// Letters first, then letters/digits with '.' or '-' for share classes (BRK.B, BF-B)
+fn is_valid_ticker(symbol: &str) -> bool {
+    let mut chars = symbol.chars();
+
+    symbol.len() <= MAX_SYMBOL_LEN
+        && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '.' || c == '-')
+}
+
+fn parse_rights(value: &str) -> Result<Vec<OptionType>, String> {
+    let mut rights = Vec::new();
+
+    for part in value.split([';', '|', ' ']).filter(|p| !p.is_empty()) {
+        let right = match part.to_uppercase().as_str() {
+            "C" | "CALL" | "CALLS" => OptionType::Call,
+            "P" | "PUT" | "PUTS" => OptionType::Put,
+            "BOTH" | "ALL" => return Ok(vec![OptionType::Call, OptionType::Put]),
+            _ => return Err(value.to_string()),
+        };
+
+        if !rights.contains(&right) {
+            rights.push(right);
+        }
+    }
+
+    if rights.is_empty() {
+        Err(value.to_string())
+    } else {
+        Ok(rights)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_text_skips_comments() {
+        let list = Watchlist::parse_text(
+            "nasdaq",
+            "# NASDAQ Stocks\nAAPL\n\nmsft  # lower case is fine\nBRK.B\n",
+        )
+        .unwrap();
+
+        assert_eq!(list.symbols(), vec!["AAPL", "MSFT", "BRK.B"]);
+    }
+
+    #[test]
+    fn test_from_symbols_skips_blank_entries() {
+        let symbols: Vec<String> = ["AAPL", " ", "msft", ""].iter().map(|s| s.to_string()).collect();
+
+        let list = Watchlist::from_symbols("NASDAQ_STOCKS", &symbols).unwrap();
+
+        assert_eq!(list.symbols(), vec!["AAPL", "MSFT"]);
+    }
+
+    #[test]
+    fn test_parse_text_reports_duplicates_and_invalid() {
+        let error = Watchlist::parse_text("bad", "AAPL\nAAPL\n12AB\n").unwrap_err();
+
+        match error {
+            WatchlistError::Invalid { problems, .. } => {
+                assert_eq!(problems.len(), 2);
+                assert!(problems[0].contains("duplicate AAPL"));
+                assert!(problems[1].contains("invalid ticker '12AB'"));
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_csv_overrides() {
+        let list = Watchlist::parse_csv(
+            "income",
+            "symbol,min_profit_percent,strike_count,rights\n\
+             AAPL,5.0,10,P\n\
+             MSFT,,,\n\
+             CSCO,1.5,,C;P\n",
+        )
+        .unwrap();
+
+        assert_eq!(list.entries[0].min_profit_percent, Some(5.0));
+        assert_eq!(list.entries[0].strike_count, Some(10));
+        assert!(!list.entries[0].allows(&OptionType::Call));
+        assert!(list.entries[0].allows(&OptionType::Put));
+
+        assert_eq!(list.entries[1], WatchlistEntry::new("MSFT"));
+        assert_eq!(list.entries[2].rights, Some(vec![OptionType::Call, OptionType::Put]));
+    }
+
+    #[test]
+    fn test_parse_csv_spread_overrides() {
+        let list = Watchlist::parse_csv(
+            "spreads",
+            "symbol,rights,spread_width,min_credit
+NVDA,,5,0.35
+AMD,P,,
+",
+        )
+        .unwrap();
+
+        assert_eq!(list.entries[0].spread_width, Some(5.0));
+        assert_eq!(list.entries[0].min_credit, Some(0.35));
+        assert_eq!(list.entries[1].spread_width, None);
+
+        assert!(Watchlist::parse_csv("bad", "symbol,spread_width
+NVDA,0
+").is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_filter_overrides() {
+        let list = Watchlist::parse_csv(
+            "liquid",
+            "symbol,min_volume,max_dte,max_spread_percent\nTSLA,500,30,8\nINTC,,,\n",
+        )
+        .unwrap();
+
+        assert_eq!(list.entries[0].filters.min_volume, Some(500));
+        assert_eq!(list.entries[0].filters.max_days_to_expiry, Some(30.0));
+        assert_eq!(list.entries[0].filters.max_spread_percent, Some(8.0));
+        assert_eq!(list.entries[1].filters, OptionChainFilters::default());
+
+        assert!(Watchlist::parse_csv("bad", "symbol,min_dte,max_dte\nTSLA,30,7\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_rejects_bad_rights() {
+        assert!(Watchlist::parse_csv("bad", "symbol,rights\nAAPL,X\n").is_err());
+    }
+
+    #[test]
+    fn test_load_named_merges_lists() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("tech.txt"), "AAPL\nMSFT\n").unwrap();
+        fs::write(dir.path().join("income.csv"), "symbol,rights\nMSFT,P\nCSCO,C\n").unwrap();
+
+        let dir = dir.path().to_str().unwrap();
+        let list = Watchlist::load_named("tech,income", dir).unwrap();
+
+        assert_eq!(list.symbols(), vec!["AAPL", "MSFT", "CSCO"]);
+        assert_eq!(Watchlist::available(dir), vec!["income", "tech"]);
+        assert!(matches!(
+            Watchlist::load_named("missing", dir),
+            Err(WatchlistError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,344 @@
+use crate::models::OptionType;
+use crate::scanner::implied_volatility::{implied_volatility, IvError};
+use crate::scanner::pricing::PricingInputs;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+// Quarterly payers are assumed to keep paying every ~13 weeks
+const DIVIDEND_INTERVAL_DAYS: i64 = 91;
+// Re-solves when backing the early exercise premium out of a market price
+const PREMIUM_ROUNDS: usize = 5;
+const PREMIUM_VOLATILITY_TOLERANCE: f64 = 1e-5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dividend {
+    pub time: f64, // years from now until the ex-date
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AmericanValuation {
+    pub value: f64,
+    pub european_value: f64,
+    pub early_exercise_premium: f64,
+}
+
+// Projects the next announced dividend forward at a quarterly cadence up to `horizon`
+pub fn project_dividends(
+    amount: f64,
+    next_ex_date: NaiveDate,
+    now: DateTime<Utc>,
+    horizon: DateTime<Utc>,
+) -> Vec<Dividend> {
+    let mut dividends = Vec::new();
+
+    if amount <= 0.0 {
+        return dividends;
+    }
+
+    let mut ex_date = next_ex_date;
+    while ex_date < now.date_naive() {
+        ex_date += Duration::days(DIVIDEND_INTERVAL_DAYS);
+    }
+
+    while ex_date <= horizon.date_naive() {
+        // Holders of record at the prior close get the dividend, so price it at the open
+        let ex_time = ex_date.and_hms_opt(13, 30, 0).map(|dt| dt.and_utc()).unwrap_or(now);
+        let time = (ex_time - now).num_seconds() as f64 / (365.0 * 24.0 * 3600.0);
+
+        dividends.push(Dividend {
+            time: time.max(0.0),
+            amount,
+        });
+        ex_date += Duration::days(DIVIDEND_INTERVAL_DAYS);
+    }
+
+    dividends
+}
+
+// Cox-Ross-Rubinstein binomial tree with escrowed discrete dividends: the tree models the
+// stock net of dividends paid before expiry and adds their present value back at each node
+// when testing for early exercise
+pub fn american_price(
+    option_type: &OptionType,
+    inputs: &PricingInputs,
+    dividends: &[Dividend],
+    steps: usize,
+) -> AmericanValuation {
+    let value = tree_value(option_type, inputs, dividends, steps, true);
+    let european_value = tree_value(option_type, inputs, dividends, steps, false);
+
+    AmericanValuation {
+        value,
+        european_value,
+        early_exercise_premium: (value - european_value).max(0.0),
+    }
+}
+
+// One dividend model per contract: with discrete dividends before expiry the stock is escrowed
+// (spot net of their present value) and the continuous yield is dropped, matching the tree
+pub fn escrowed_inputs(inputs: &PricingInputs, dividends: &[Dividend]) -> PricingInputs {
+    let paid = dividends_before_expiry(inputs, dividends);
+
+    if paid.is_empty() {
+        return *inputs;
+    }
+
+    let pv_dividends: f64 = paid
+        .iter()
+        .map(|d| d.amount * (-inputs.rate * d.time).exp())
+        .sum();
+
+    PricingInputs {
+        spot: inputs.spot - pv_dividends,
+        dividend_yield: 0.0,
+        ..*inputs
+    }
+}
+
+// Volatility at which the tree value matches `price`. Black-Scholes is solved on the price net
+// of the early exercise premium, and the premium re-estimated at each new volatility, so a
+// deep ITM put or a call ahead of a dividend isn't read as extra volatility.
+// `inputs.volatility` is ignored.
+pub fn american_implied_volatility(
+    option_type: &OptionType,
+    price: f64,
+    inputs: &PricingInputs,
+    dividends: &[Dividend],
+    steps: usize,
+) -> Result<f64, IvError> {
+    let european = escrowed_inputs(inputs, dividends);
+    let mut volatility = implied_volatility(option_type, price, &european)?;
+
+    for _ in 0..PREMIUM_ROUNDS {
+        let at_volatility = PricingInputs { volatility, ..*inputs };
+        let premium = american_price(option_type, &at_volatility, dividends, steps)
+            .early_exercise_premium;
+
+        if premium <= 0.0 {
+            break;
+        }
+
+        // Past the European floor the premium estimate overshot; keep the last solve
+        let Ok(next) = implied_volatility(option_type, price - premium, &european) else {
+            break;
+        };
+
+        let converged = (next - volatility).abs() < PREMIUM_VOLATILITY_TOLERANCE;
+        volatility = next;
+
+        if converged {
+            break;
+        }
+    }
+
+    Ok(volatility)
+}
+
+fn dividends_before_expiry(inputs: &PricingInputs, dividends: &[Dividend]) -> Vec<Dividend> {
+    dividends
+        .iter()
+        .copied()
+        .filter(|d| d.time >= 0.0 && d.time <= inputs.time_to_expiry && d.amount > 0.0)
+        .collect()
+}
+
+fn tree_value(
+    option_type: &OptionType,
+    inputs: &PricingInputs,
+    dividends: &[Dividend],
+    steps: usize,
+    american: bool,
+) -> f64 {
+    let t = inputs.time_to_expiry;
+    let payoff = |spot: f64| match option_type {
+        OptionType::Call => (spot - inputs.strike).max(0.0),
+        OptionType::Put => (inputs.strike - spot).max(0.0),
+    };
+
+    let dividends_before_expiry = dividends_before_expiry(inputs, dividends);
+
+    // Discrete dividends replace the continuous yield rather than adding to it
+    let dividend_yield = if dividends_before_expiry.is_empty() {
+        inputs.dividend_yield
+    } else {
+        0.0
+    };
+
+    let pv_dividends_after = |time: f64| -> f64 {
+        dividends_before_expiry
+            .iter()
+            .filter(|d| d.time > time)
+            .map(|d| d.amount * (-inputs.rate * (d.time - time)).exp())
+            .sum()
+    };
+
+    let spot_ex_dividends = inputs.spot - pv_dividends_after(0.0);
+
+    if t <= 0.0 || inputs.volatility <= 0.0 || spot_ex_dividends <= 0.0 || inputs.strike <= 0.0 {
+        return payoff(inputs.spot);
+    }
+
+    let steps = steps.max(1);
+    let dt = t / steps as f64;
+    let up = (inputs.volatility * dt.sqrt()).exp();
+    let down = 1.0 / up;
+    let growth = ((inputs.rate - dividend_yield) * dt).exp();
+    let p = ((growth - down) / (up - down)).clamp(0.0, 1.0);
+    let discount = (-inputs.rate * dt).exp();
+
+    let node_spot = |step: usize, ups: usize| {
+        spot_ex_dividends * up.powi(ups as i32) * down.powi((step - ups) as i32)
+    };
+
+    let mut values: Vec<f64> = (0..=steps).map(|j| payoff(node_spot(steps, j))).collect();
+
+    for step in (0..steps).rev() {
+        let pv_remaining = pv_dividends_after(step as f64 * dt);
+
+        for j in 0..=step {
+            let continuation = discount * (p * values[j + 1] + (1.0 - p) * values[j]);
+
+            values[j] = if american {
+                continuation.max(payoff(node_spot(step, j) + pv_remaining))
+            } else {
+                continuation
+            };
+        }
+    }
+
+    values[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::pricing::black_scholes;
+
+    fn inputs() -> PricingInputs {
+        PricingInputs {
+            spot: 100.0,
+            strike: 100.0,
+            time_to_expiry: 0.5,
+            rate: 0.05,
+            dividend_yield: 0.0,
+            volatility: 0.25,
+        }
+    }
+
+    #[test]
+    fn test_call_without_dividends_matches_black_scholes() {
+        let valuation = american_price(&OptionType::Call, &inputs(), &[], 500);
+        let european = black_scholes(&OptionType::Call, &inputs()).value;
+
+        assert!((valuation.value - european).abs() < 0.02);
+        assert!(valuation.early_exercise_premium < 1e-9);
+    }
+
+    #[test]
+    fn test_put_has_early_exercise_premium() {
+        let deep_itm = PricingInputs {
+            strike: 130.0,
+            ..inputs()
+        };
+
+        let valuation = american_price(&OptionType::Put, &deep_itm, &[], 300);
+        let european = black_scholes(&OptionType::Put, &deep_itm).value;
+
+        assert!(valuation.value > european);
+        assert!(valuation.early_exercise_premium > 0.1);
+        assert!(valuation.value >= 30.0);
+    }
+
+    #[test]
+    fn test_call_before_large_dividend_is_exercised_early() {
+        let itm = PricingInputs {
+            strike: 80.0,
+            ..inputs()
+        };
+        let dividends = [Dividend {
+            time: 0.4,
+            amount: 5.0,
+        }];
+
+        let valuation = american_price(&OptionType::Call, &itm, &dividends, 300);
+
+        assert!(valuation.early_exercise_premium > 0.05);
+        assert!(valuation.european_value < american_price(&OptionType::Call, &itm, &[], 300).value);
+    }
+
+    #[test]
+    fn test_discrete_dividends_replace_the_yield() {
+        let dividends = [Dividend {
+            time: 0.25,
+            amount: 1.0,
+        }];
+        let with_yield = PricingInputs {
+            dividend_yield: 0.03,
+            ..inputs()
+        };
+
+        let counted_once = american_price(&OptionType::Call, &inputs(), &dividends, 300);
+        let configured = american_price(&OptionType::Call, &with_yield, &dividends, 300);
+
+        assert!((configured.value - counted_once.value).abs() < 1e-12);
+        // Without a schedule the yield still applies
+        assert!(
+            american_price(&OptionType::Call, &with_yield, &[], 300).value
+                < american_price(&OptionType::Call, &inputs(), &[], 300).value
+        );
+    }
+
+    #[test]
+    fn test_escrowed_inputs_match_the_tree() {
+        let dividends = [Dividend {
+            time: 0.25,
+            amount: 2.0,
+        }];
+        let with_yield = PricingInputs {
+            dividend_yield: 0.03,
+            ..inputs()
+        };
+
+        let escrowed = escrowed_inputs(&with_yield, &dividends);
+        let european = american_price(&OptionType::Put, &with_yield, &dividends, 500).european_value;
+
+        assert_eq!(escrowed.dividend_yield, 0.0);
+        assert!((escrowed.spot - (100.0 - 2.0 * (-0.05_f64 * 0.25).exp())).abs() < 1e-9);
+        assert!((black_scholes(&OptionType::Put, &escrowed).value - european).abs() < 0.02);
+        assert_eq!(escrowed_inputs(&with_yield, &[]), with_yield);
+    }
+
+    #[test]
+    fn test_american_iv_recovers_tree_volatility() {
+        let deep_itm = PricingInputs {
+            strike: 130.0,
+            volatility: 0.3,
+            ..inputs()
+        };
+        let price = american_price(&OptionType::Put, &deep_itm, &[], 200).value;
+
+        let solved = american_implied_volatility(&OptionType::Put, price, &deep_itm, &[], 200);
+        let european = implied_volatility(&OptionType::Put, price, &deep_itm).unwrap();
+
+        assert!((solved.unwrap() - 0.3).abs() < 0.005);
+        // The European solve reads the early exercise premium as volatility
+        assert!(european > 0.31);
+    }
+
+    #[test]
+    fn test_project_dividends_quarterly() {
+        let now = NaiveDate::from_ymd_opt(2025, 1, 2)
+            .unwrap()
+            .and_hms_opt(15, 0, 0)
+            .unwrap()
+            .and_utc();
+        let horizon = now + Duration::days(200);
+        let ex_date = NaiveDate::from_ymd_opt(2024, 11, 8).unwrap();
+
+        let dividends = project_dividends(0.25, ex_date, now, horizon);
+
+        // Nov 8 rolls to Feb 7, then May 9; Aug 8 is past the horizon
+        assert_eq!(dividends.len(), 2);
+        assert!(dividends[0].time > 0.09 && dividends[0].time < 0.11);
+    }
+}
@@ -0,0 +1,295 @@
+use crate::models::{CalendarSpreadConfig, OptionChain, OptionType};
+use crate::scanner::strategy::{
+    Candidate, CandidateDetail, Strategy, StrategyInput, CALENDAR_SPREADS,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum CalendarKind {
+    Calendar,
+    Diagonal,
+}
+
+impl fmt::Display for CalendarKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalendarKind::Calendar => write!(f, "Calendar"),
+            CalendarKind::Diagonal => write!(f, "Diagonal"),
+        }
+    }
+}
+
+// Short the front expiry, long a later one of the same right. `iv_differential` is front minus
+// back IV in vol points; `net_debit` is per share and net of trading costs; theta (per day)
+// and vega (per vol point) are the spread's net exposure per contract pair.
+#[derive(Debug, Clone, Serialize)]
+pub struct CalendarSpread {
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub kind: CalendarKind,
+    pub option_type: OptionType,
+    pub front_expiration: DateTime<Utc>,
+    pub back_expiration: DateTime<Utc>,
+    pub front_strike: f64,
+    pub back_strike: f64,
+    pub front_iv: f64,
+    pub back_iv: f64,
+    pub iv_differential: f64,
+    pub net_debit: f64,
+    pub max_loss: f64,
+    pub theta: f64,
+    pub vega: f64,
+    pub stock_price: f64,
+    pub front_contract_id: String,
+    pub back_contract_id: String,
+}
+
+// Pairs every front expiry with each later one on the same strike (calendars) and the
+// neighbouring strikes (diagonals), richest front-month IV relative to back-month first
+pub fn evaluate_calendar_spreads(
+    chains: &[OptionChain],
+    config: &CalendarSpreadConfig,
+) -> Vec<CalendarSpread> {
+    let legs: Vec<&OptionChain> = chains
+        .iter()
+        .filter(|chain| chain.option_price > 0.0 && chain.iv_status.is_usable())
+        .collect();
+
+    let mut spreads = Vec::new();
+
+    for front in &legs {
+        for back in &legs {
+            if back.option_type != front.option_type || back.expiration <= front.expiration {
+                continue;
+            }
+
+            let kind = if back.strike == front.strike {
+                CalendarKind::Calendar
+            } else if config.include_diagonals && is_adjacent(&legs, front, back) {
+                CalendarKind::Diagonal
+            } else {
+                continue;
+            };
+
+            spreads.extend(calendar_spread(kind, front, back));
+        }
+    }
+
+    spreads.retain(|spread| spread.iv_differential >= config.min_iv_differential);
+    spreads.sort_by(|a, b| b.iv_differential.total_cmp(&a.iv_differential));
+    spreads.truncate(config.top_per_symbol);
+    spreads
+}
+
+// True when no other back-month strike of the same right sits between the two strikes
+fn is_adjacent(legs: &[&OptionChain], front: &OptionChain, back: &OptionChain) -> bool {
+    let (low, high) = if front.strike < back.strike {
+        (front.strike, back.strike)
+    } else {
+        (back.strike, front.strike)
+    };
+
+    !legs.iter().any(|leg| {
+        leg.option_type == back.option_type
+            && leg.expiration == back.expiration
+            && leg.strike > low
+            && leg.strike < high
+    })
+}
+
+fn calendar_spread(
+    kind: CalendarKind,
+    front: &OptionChain,
+    back: &OptionChain,
+) -> Option<CalendarSpread> {
+    let net_debit = back.cost_basis() - front.short_proceeds();
+
+    // Front premium at or above the back means a quote problem, not free money
+    if net_debit <= 0.0 {
+        return None;
+    }
+
+    let multiplier = back.multiplier.max(1.0);
+
+    // A diagonal whose long leg is further OTM than the short one can also lose the strike gap
+    // when the front is assigned
+    let strike_gap = match back.option_type {
+        OptionType::Call => (back.strike - front.strike).max(0.0),
+        OptionType::Put => (front.strike - back.strike).max(0.0),
+    };
+
+    Some(CalendarSpread {
+        timestamp: back.timestamp,
+        symbol: back.symbol.clone(),
+        kind,
+        option_type: back.option_type.clone(),
+        front_expiration: front.expiration,
+        back_expiration: back.expiration,
+        front_strike: front.strike,
+        back_strike: back.strike,
+        front_iv: front.implied_volatility,
+        back_iv: back.implied_volatility,
+        iv_differential: (front.implied_volatility - back.implied_volatility) * 100.0,
+        net_debit,
+        max_loss: (net_debit + strike_gap) * multiplier,
+        theta: (back.theta - front.theta) * multiplier,
+        vega: (back.vega - front.vega) * multiplier,
+        stock_price: back.stock_price,
+        front_contract_id: front.contract_id.clone(),
+        back_contract_id: back.contract_id.clone(),
+    })
+}
+
+pub struct CalendarSpreads(pub CalendarSpreadConfig);
+
+impl Strategy for CalendarSpreads {
+    fn name(&self) -> &str {
+        CALENDAR_SPREADS
+    }
+
+    // Back months trade less than the front, so their legs would rarely pass the filters
+    fn uses_contract_filters(&self) -> bool {
+        false
+    }
+
+    fn evaluate(&self, input: &StrategyInput) -> Vec<Candidate> {
+        evaluate_calendar_spreads(input.chains, &self.0)
+            .into_iter()
+            .map(|spread| Candidate {
+                timestamp: spread.timestamp,
+                strategy: CALENDAR_SPREADS.to_string(),
+                symbol: spread.symbol.clone(),
+                score: spread.iv_differential,
+                contract_ids: vec![
+                    spread.front_contract_id.clone(),
+                    spread.back_contract_id.clone(),
+                ],
+                metrics: vec![
+                    ("iv_differential".to_string(), spread.iv_differential),
+                    ("net_debit".to_string(), spread.net_debit),
+                    ("theta".to_string(), spread.theta),
+                    ("vega".to_string(), spread.vega),
+                ],
+                detail: CandidateDetail::Calendar(spread),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leg(
+        days: i64,
+        strike: f64,
+        option_price: f64,
+        iv: f64,
+        theta: f64,
+        vega: f64,
+    ) -> OptionChain {
+        OptionChain {
+            expiration: DateTime::from_timestamp(1_750_000_000 + days * 86_400, 0).unwrap(),
+            implied_volatility: iv,
+            theta,
+            vega,
+            ..OptionChain::sample(OptionType::Call, strike, option_price)
+        }
+        .with_sample_id()
+    }
+
+    #[test]
+    fn test_calendar_metrics() {
+        let chains = [
+            leg(7, 100.0, 2.0, 0.55, -0.12, 0.05),
+            leg(35, 100.0, 4.5, 0.40, -0.05, 0.12),
+        ];
+
+        let spreads = evaluate_calendar_spreads(&chains, &CalendarSpreadConfig::default());
+
+        assert_eq!(spreads.len(), 1);
+        let spread = &spreads[0];
+        assert_eq!(spread.kind, CalendarKind::Calendar);
+        assert!((spread.iv_differential - 15.0).abs() < 1e-9);
+        assert!((spread.net_debit - 2.5).abs() < 1e-9);
+        assert!((spread.max_loss - 250.0).abs() < 1e-9);
+        // Short front decays faster than the long back: positive theta, long vega
+        assert!((spread.theta - 7.0).abs() < 1e-9);
+        assert!((spread.vega - 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_diagonals_use_adjacent_strikes_only() {
+        let chains = [
+            leg(7, 100.0, 2.0, 0.50, 0.0, 0.0),
+            leg(35, 95.0, 7.0, 0.42, 0.0, 0.0),
+            leg(35, 100.0, 4.5, 0.40, 0.0, 0.0),
+            leg(35, 105.0, 2.6, 0.38, 0.0, 0.0),
+            leg(35, 110.0, 1.4, 0.37, 0.0, 0.0),
+        ];
+
+        let spreads = evaluate_calendar_spreads(&chains, &CalendarSpreadConfig::default());
+        let mut back_strikes: Vec<f64> = spreads.iter().map(|s| s.back_strike).collect();
+        back_strikes.sort_by(f64::total_cmp);
+
+        assert_eq!(back_strikes, vec![95.0, 100.0, 105.0]);
+
+        let calendars_only = CalendarSpreadConfig {
+            include_diagonals: false,
+            ..CalendarSpreadConfig::default()
+        };
+        assert_eq!(evaluate_calendar_spreads(&chains, &calendars_only).len(), 1);
+    }
+
+    #[test]
+    fn test_diagonal_max_loss_includes_strike_gap() {
+        let chains = [
+            leg(7, 100.0, 2.0, 0.50, 0.0, 0.0),
+            leg(35, 95.0, 7.0, 0.42, 0.0, 0.0),
+            leg(35, 105.0, 2.6, 0.38, 0.0, 0.0),
+        ];
+
+        let spreads = evaluate_calendar_spreads(&chains, &CalendarSpreadConfig::default());
+        let loss = |strike: f64| spreads.iter().find(|s| s.back_strike == strike).unwrap().max_loss;
+
+        // Long 95 call under a short 100: only the debit is at risk
+        assert!((loss(95.0) - 500.0).abs() < 1e-9);
+        // Long 105 call over a short 100: debit 0.60 plus the 5 point gap
+        assert!((loss(105.0) - 560.0).abs() < 1e-9);
+
+        let puts: Vec<OptionChain> = chains
+            .iter()
+            .map(|chain| {
+                OptionChain { option_type: OptionType::Put, ..chain.clone() }.with_sample_id()
+            })
+            .collect();
+        let spreads = evaluate_calendar_spreads(&puts, &CalendarSpreadConfig::default());
+        let loss = |strike: f64| spreads.iter().find(|s| s.back_strike == strike).unwrap().max_loss;
+
+        // Mirrored for puts: the lower long strike carries the gap
+        assert!((loss(95.0) - 1000.0).abs() < 1e-9);
+        assert!((loss(105.0) - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ranked_by_front_month_richness() {
+        let chains = [
+            leg(7, 100.0, 2.0, 0.45, 0.0, 0.0),
+            leg(14, 100.0, 2.8, 0.60, 0.0, 0.0),
+            leg(35, 100.0, 4.5, 0.40, 0.0, 0.0),
+        ];
+        let config = CalendarSpreadConfig {
+            min_iv_differential: 1.0,
+            ..CalendarSpreadConfig::default()
+        };
+
+        let spreads = evaluate_calendar_spreads(&chains, &config);
+
+        // 14/35 (+20) beats 7/35 (+5); 7/14 is inverted (-15) and filtered out
+        assert_eq!(spreads.len(), 2);
+        assert!((spreads[0].iv_differential - 20.0).abs() < 1e-9);
+        assert!((spreads[1].iv_differential - 5.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::models::{CashSecuredPutConfig, OptionChain, OptionType};
+use crate::scanner::strategy::{
+    Candidate, CandidateDetail, Strategy, StrategyInput, CASH_SECURED_PUTS,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+// Sell one put with the full strike held in cash. Percentages are per share of collateral
+// (the strike); premium is net of trading costs.
+#[derive(Debug, Clone, Serialize)]
+pub struct CashSecuredPut {
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub expiration: DateTime<Utc>,
+    pub strike: f64,
+    pub stock_price: f64,
+    pub premium: f64,
+    pub collateral: f64, // per contract
+    pub premium_yield: f64,
+    pub effective_purchase_price: f64,
+    pub distance_from_spot: f64, // percent below spot, negative when ITM
+    pub annualized_return: f64,
+    pub days_to_expiry: f64,
+    pub delta: f64,
+    pub prob_assignment: f64,
+    pub contract_id: String,
+}
+
+// Puts with at least `min_yield` on collateral and no more than `max_delta`, best annualized
+// return first. A put without a usable IV has zero-vol Greeks, so its delta and assignment
+// probability mean nothing and it is skipped.
+pub fn evaluate_cash_secured_puts(
+    chains: &[OptionChain],
+    thresholds: &CashSecuredPutConfig,
+) -> Vec<CashSecuredPut> {
+    let mut candidates: Vec<CashSecuredPut> = chains
+        .iter()
+        .filter(|chain| chain.option_type == OptionType::Put && chain.iv_status.is_usable())
+        .filter(|chain| chain.delta.abs() <= thresholds.max_delta)
+        .filter_map(cash_secured_put)
+        .filter(|candidate| candidate.premium_yield >= thresholds.min_yield)
+        .collect();
+
+    candidates.sort_by(|a, b| b.annualized_return.total_cmp(&a.annualized_return));
+    candidates
+}
+
+fn cash_secured_put(chain: &OptionChain) -> Option<CashSecuredPut> {
+    let spot = chain.stock_price;
+    let premium = chain.short_proceeds();
+
+    if spot <= 0.0 || chain.strike <= 0.0 || premium <= 0.0 {
+        return None;
+    }
+
+    let premium_yield = premium / chain.strike * 100.0;
+    let days_to_expiry = chain.metrics.days_to_expiry;
+
+    Some(CashSecuredPut {
+        timestamp: chain.timestamp,
+        symbol: chain.symbol.clone(),
+        expiration: chain.expiration,
+        strike: chain.strike,
+        stock_price: spot,
+        premium,
+        collateral: chain.strike * chain.multiplier,
+        premium_yield,
+        effective_purchase_price: chain.strike - premium,
+        distance_from_spot: (spot - chain.strike) / spot * 100.0,
+        annualized_return: premium_yield * 365.0 / days_to_expiry.max(1.0),
+        days_to_expiry,
+        delta: chain.delta,
+        prob_assignment: chain.prob_itm,
+        contract_id: chain.contract_id.clone(),
+    })
+}
+
+pub struct CashSecuredPuts(pub CashSecuredPutConfig);
+
+impl Strategy for CashSecuredPuts {
+    fn name(&self) -> &str {
+        CASH_SECURED_PUTS
+    }
+
+    fn evaluate(&self, input: &StrategyInput) -> Vec<Candidate> {
+        evaluate_cash_secured_puts(input.chains, &self.0)
+            .into_iter()
+            .map(|put| Candidate {
+                timestamp: put.timestamp,
+                strategy: CASH_SECURED_PUTS.to_string(),
+                symbol: put.symbol.clone(),
+                score: put.annualized_return,
+                contract_ids: vec![put.contract_id.clone()],
+                metrics: vec![
+                    ("premium_yield".to_string(), put.premium_yield),
+                    ("annualized_return".to_string(), put.annualized_return),
+                    ("distance_from_spot".to_string(), put.distance_from_spot),
+                    ("prob_assignment".to_string(), put.prob_assignment),
+                ],
+                detail: CandidateDetail::CashSecuredPut(put),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{IvStatus, OptionMetrics};
+
+    fn put(strike: f64, option_price: f64, delta: f64) -> OptionChain {
+        OptionChain {
+            delta,
+            metrics: OptionMetrics {
+                days_to_expiry: 30.0,
+                ..OptionMetrics::default()
+            },
+            ..OptionChain::sample(OptionType::Put, strike, option_price)
+        }
+    }
+
+    #[test]
+    fn test_cash_secured_put_metrics() {
+        let chain = OptionChain {
+            prob_itm: 0.2,
+            ..put(95.0, 1.9, -0.25)
+        };
+        let candidates = evaluate_cash_secured_puts(&[chain], &CashSecuredPutConfig::default());
+        let candidate = &candidates[0];
+
+        assert_eq!(candidate.collateral, 9_500.0);
+        assert!((candidate.premium_yield - 2.0).abs() < 1e-9);
+        assert!((candidate.effective_purchase_price - 93.1).abs() < 1e-9);
+        assert!((candidate.distance_from_spot - 5.0).abs() < 1e-9);
+        assert!((candidate.annualized_return - 2.0 * 365.0 / 30.0).abs() < 1e-9);
+        assert_eq!(candidate.prob_assignment, 0.2);
+    }
+
+    #[test]
+    fn test_filters_by_yield_and_delta() {
+        let chains = [
+            put(100.0, 4.0, -0.50), // too much delta
+            put(95.0, 1.9, -0.25),
+            put(85.0, 0.4, -0.08), // yield too low
+        ];
+        let thresholds = CashSecuredPutConfig {
+            min_yield: 1.0,
+            max_delta: 0.30,
+        };
+
+        let candidates = evaluate_cash_secured_puts(&chains, &thresholds);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].strike, 95.0);
+    }
+
+    #[test]
+    fn test_skips_puts_without_a_usable_iv() {
+        // A failed solve leaves an OTM put at zero delta and zero assignment probability
+        let failed = OptionChain {
+            iv_status: IvStatus::Failed,
+            prob_itm: 0.0,
+            ..put(90.0, 1.5, 0.0)
+        };
+
+        let candidates = evaluate_cash_secured_puts(&[failed], &CashSecuredPutConfig::default());
+
+        assert!(candidates.is_empty());
+    }
+}
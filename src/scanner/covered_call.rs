@@ -0,0 +1,174 @@
+use crate::models::{CoveredCallConfig, OptionChain, OptionType};
+use crate::scanner::strategy::{Candidate, CandidateDetail, Strategy, StrategyInput, COVERED_CALLS};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+// Calls struck up to this far below spot still count as at the money
+const ATM_TOLERANCE: f64 = 0.01;
+
+// Buy 100 shares at spot and sell one call against them. Percentages are per share of the
+// net debit (spot minus premium); premium is net of trading costs.
+#[derive(Debug, Clone, Serialize)]
+pub struct CoveredCall {
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub expiration: DateTime<Utc>,
+    pub strike: f64,
+    pub stock_price: f64,
+    pub premium: f64,
+    pub breakeven: f64,
+    pub static_return: f64,
+    pub if_called_return: f64,
+    pub downside_protection: f64,
+    pub annualized_yield: f64,
+    pub days_to_expiry: f64,
+    pub delta: f64,
+    pub prob_called: f64,
+    pub contract_id: String,
+}
+
+// OTM and ATM calls that clear every threshold, best annualized yield first. Calls without a
+// usable IV are skipped: their zero-vol Greeks would show no chance of being called.
+pub fn evaluate_covered_calls(
+    chains: &[OptionChain],
+    thresholds: &CoveredCallConfig,
+) -> Vec<CoveredCall> {
+    let mut candidates: Vec<CoveredCall> = chains
+        .iter()
+        .filter(|chain| chain.option_type == OptionType::Call && chain.iv_status.is_usable())
+        .filter(|chain| chain.strike >= chain.stock_price * (1.0 - ATM_TOLERANCE))
+        .filter_map(covered_call)
+        .filter(|candidate| {
+            candidate.static_return >= thresholds.min_static_return
+                && candidate.if_called_return >= thresholds.min_if_called_return
+                && candidate.downside_protection >= thresholds.min_downside_protection
+                && candidate.annualized_yield >= thresholds.min_annualized_yield
+        })
+        .collect();
+
+    candidates.sort_by(|a, b| b.annualized_yield.total_cmp(&a.annualized_yield));
+    candidates
+}
+
+fn covered_call(chain: &OptionChain) -> Option<CoveredCall> {
+    let spot = chain.stock_price;
+    let premium = chain.short_proceeds();
+
+    if spot <= 0.0 || premium <= 0.0 {
+        return None;
+    }
+
+    let net_debit = spot - premium;
+    let static_return = premium / net_debit * 100.0;
+    let if_called_return = (premium + chain.strike - spot) / net_debit * 100.0;
+    let days_to_expiry = chain.metrics.days_to_expiry;
+
+    Some(CoveredCall {
+        timestamp: chain.timestamp,
+        symbol: chain.symbol.clone(),
+        expiration: chain.expiration,
+        strike: chain.strike,
+        stock_price: spot,
+        premium,
+        breakeven: net_debit,
+        static_return,
+        if_called_return,
+        downside_protection: premium / spot * 100.0,
+        annualized_yield: static_return * 365.0 / days_to_expiry.max(1.0),
+        days_to_expiry,
+        delta: chain.delta,
+        prob_called: chain.prob_itm,
+        contract_id: chain.contract_id.clone(),
+    })
+}
+
+pub struct CoveredCalls(pub CoveredCallConfig);
+
+impl Strategy for CoveredCalls {
+    fn name(&self) -> &str {
+        COVERED_CALLS
+    }
+
+    fn evaluate(&self, input: &StrategyInput) -> Vec<Candidate> {
+        evaluate_covered_calls(input.chains, &self.0)
+            .into_iter()
+            .map(|call| Candidate {
+                timestamp: call.timestamp,
+                strategy: COVERED_CALLS.to_string(),
+                symbol: call.symbol.clone(),
+                score: call.annualized_yield,
+                contract_ids: vec![call.contract_id.clone()],
+                metrics: vec![
+                    ("static_return".to_string(), call.static_return),
+                    ("if_called_return".to_string(), call.if_called_return),
+                    ("annualized_yield".to_string(), call.annualized_yield),
+                    ("prob_called".to_string(), call.prob_called),
+                ],
+                detail: CandidateDetail::CoveredCall(call),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{IvStatus, OptionMetrics};
+
+    fn call(strike: f64, option_price: f64) -> OptionChain {
+        OptionChain {
+            metrics: OptionMetrics {
+                days_to_expiry: 36.5,
+                ..OptionMetrics::default()
+            },
+            ..OptionChain::sample(OptionType::Call, strike, option_price)
+        }
+    }
+
+    #[test]
+    fn test_covered_call_returns() {
+        let candidates = evaluate_covered_calls(&[call(105.0, 2.0)], &CoveredCallConfig::default());
+        let candidate = &candidates[0];
+
+        assert_eq!(candidate.breakeven, 98.0);
+        assert!((candidate.static_return - 2.0 / 98.0 * 100.0).abs() < 1e-9);
+        assert!((candidate.if_called_return - 7.0 / 98.0 * 100.0).abs() < 1e-9);
+        assert!((candidate.downside_protection - 2.0).abs() < 1e-9);
+        assert!((candidate.annualized_yield - candidate.static_return * 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_skips_itm_calls_and_applies_thresholds() {
+        let chains = [call(90.0, 11.0), call(100.0, 3.0), call(110.0, 0.5)];
+        let thresholds = CoveredCallConfig {
+            min_static_return: 1.0,
+            ..CoveredCallConfig::default()
+        };
+
+        let candidates = evaluate_covered_calls(&chains, &thresholds);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].strike, 100.0);
+    }
+
+    #[test]
+    fn test_trading_costs_reduce_premium() {
+        let mut chain = call(105.0, 2.0);
+        chain.trading_cost = 50.0;
+
+        let candidates = evaluate_covered_calls(&[chain], &CoveredCallConfig::default());
+
+        assert!((candidates[0].premium - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_skips_calls_without_a_usable_iv() {
+        // A failed solve leaves delta and prob_itm at zero
+        let failed = OptionChain {
+            iv_status: IvStatus::Failed,
+            ..call(105.0, 2.0)
+        };
+
+        assert!(evaluate_covered_calls(&[failed], &CoveredCallConfig::default()).is_empty());
+    }
+}
@@ -0,0 +1,308 @@
+use crate::models::{CycleDiffConfig, OptionChain, OptionType};
+use crate::scanner::strategy::Candidate;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum ChangeKind {
+    New,
+    Changed,
+    Gone,
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeKind::New => write!(f, "New"),
+            ChangeKind::Changed => write!(f, "Changed"),
+            ChangeKind::Gone => write!(f, "Gone"),
+        }
+    }
+}
+
+// One opportunity that appeared, disappeared or moved since the previous cycle. `previous_*`
+// is empty for new contracts and `current_*` for gone ones; IV is in percent.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpportunityChange {
+    pub timestamp: DateTime<Utc>,
+    pub change: ChangeKind,
+    pub symbol: String,
+    pub option_type: OptionType,
+    pub expiration: DateTime<Utc>,
+    pub strike: f64,
+    pub previous_price: Option<f64>,
+    pub current_price: Option<f64>,
+    pub previous_profit: Option<f64>,
+    pub current_profit: Option<f64>,
+    pub previous_iv: Option<f64>,
+    pub current_iv: Option<f64>,
+    pub contract_id: String,
+}
+
+// A strategy candidate that appeared, dropped out or re-scored, identified by its strategy and
+// legs. Scores are the strategy's own, before ranking rescales them.
+#[derive(Debug, Clone, Serialize)]
+pub struct CandidateChange {
+    pub timestamp: DateTime<Utc>,
+    pub change: ChangeKind,
+    pub strategy: String,
+    pub symbol: String,
+    pub contract_ids: String, // legs joined with '+'
+    pub previous_score: Option<f64>,
+    pub current_score: Option<f64>,
+}
+
+// New and materially changed contracts in current order, then the ones that dropped out
+pub fn diff_options(
+    previous: &[OptionChain],
+    current: &[OptionChain],
+    thresholds: &CycleDiffConfig,
+) -> Vec<OpportunityChange> {
+    let timestamp = Utc::now();
+    let before: HashMap<&str, &OptionChain> = previous
+        .iter()
+        .map(|option| (option.contract_id.as_str(), option))
+        .collect();
+    let now: HashSet<&str> = current.iter().map(|option| option.contract_id.as_str()).collect();
+
+    let mut changes: Vec<OpportunityChange> = current
+        .iter()
+        .filter_map(|option| match before.get(option.contract_id.as_str()) {
+            None => Some(change(timestamp, ChangeKind::New, None, Some(option))),
+            Some(old) if is_material(old, option, thresholds) => {
+                Some(change(timestamp, ChangeKind::Changed, Some(old), Some(option)))
+            }
+            Some(_) => None,
+        })
+        .collect();
+
+    changes.extend(
+        previous
+            .iter()
+            .filter(|option| !now.contains(option.contract_id.as_str()))
+            .map(|option| change(timestamp, ChangeKind::Gone, Some(option), None)),
+    );
+
+    changes
+}
+
+// Same shape as diff_options; a candidate counts as changed when its score moves by
+// `min_price_change_percent` of the previous score
+pub fn diff_candidates(
+    previous: &[Candidate],
+    current: &[Candidate],
+    thresholds: &CycleDiffConfig,
+) -> Vec<CandidateChange> {
+    let timestamp = Utc::now();
+    let before: HashMap<(&str, String), &Candidate> = previous
+        .iter()
+        .map(|candidate| (candidate_key(candidate), candidate))
+        .collect();
+    let now: HashSet<(&str, String)> = current.iter().map(candidate_key).collect();
+
+    let mut changes: Vec<CandidateChange> = current
+        .iter()
+        .filter_map(|candidate| match before.get(&candidate_key(candidate)) {
+            None => Some(candidate_change(timestamp, ChangeKind::New, None, Some(candidate))),
+            Some(old)
+                if exceeds(
+                    percent_change(old.score, candidate.score),
+                    thresholds.min_price_change_percent,
+                ) =>
+            {
+                Some(candidate_change(timestamp, ChangeKind::Changed, Some(old), Some(candidate)))
+            }
+            Some(_) => None,
+        })
+        .collect();
+
+    changes.extend(
+        previous
+            .iter()
+            .filter(|candidate| !now.contains(&candidate_key(candidate)))
+            .map(|candidate| candidate_change(timestamp, ChangeKind::Gone, Some(candidate), None)),
+    );
+
+    changes
+}
+
+fn candidate_key(candidate: &Candidate) -> (&str, String) {
+    (candidate.strategy.as_str(), candidate.contract_ids.join("+"))
+}
+
+fn exceeds(change: f64, threshold: f64) -> bool {
+    change > 0.0 && change >= threshold
+}
+
+// Relative to the old value; from zero, absolute change counts in hundredths
+fn percent_change(old: f64, new: f64) -> f64 {
+    if old != 0.0 {
+        (new - old).abs() / old.abs() * 100.0
+    } else {
+        (new - old).abs() * 100.0
+    }
+}
+
+fn is_material(old: &OptionChain, new: &OptionChain, thresholds: &CycleDiffConfig) -> bool {
+    let price_change = percent_change(old.option_price, new.option_price);
+    let profit_change = (new.profit_percent - old.profit_percent).abs();
+    let iv_change = (new.implied_volatility - old.implied_volatility).abs() * 100.0;
+
+    exceeds(price_change, thresholds.min_price_change_percent)
+        || exceeds(profit_change, thresholds.min_profit_change)
+        || exceeds(iv_change, thresholds.min_iv_change)
+}
+
+fn change(
+    timestamp: DateTime<Utc>,
+    kind: ChangeKind,
+    old: Option<&OptionChain>,
+    new: Option<&OptionChain>,
+) -> OpportunityChange {
+    // At least one side is always present
+    let contract = new.or(old).expect("change without a contract");
+
+    OpportunityChange {
+        timestamp,
+        change: kind,
+        symbol: contract.symbol.clone(),
+        option_type: contract.option_type.clone(),
+        expiration: contract.expiration,
+        strike: contract.strike,
+        previous_price: old.map(|option| option.option_price),
+        current_price: new.map(|option| option.option_price),
+        previous_profit: old.map(|option| option.profit_percent),
+        current_profit: new.map(|option| option.profit_percent),
+        previous_iv: old.map(|option| option.implied_volatility * 100.0),
+        current_iv: new.map(|option| option.implied_volatility * 100.0),
+        contract_id: contract.contract_id.clone(),
+    }
+}
+
+fn candidate_change(
+    timestamp: DateTime<Utc>,
+    kind: ChangeKind,
+    old: Option<&Candidate>,
+    new: Option<&Candidate>,
+) -> CandidateChange {
+    let candidate = new.or(old).expect("change without a candidate");
+
+    CandidateChange {
+        timestamp,
+        change: kind,
+        strategy: candidate.strategy.clone(),
+        symbol: candidate.symbol.clone(),
+        contract_ids: candidate.contract_ids.join("+"),
+        previous_score: old.map(|candidate| candidate.score),
+        current_score: new.map(|candidate| candidate.score),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(contract_id: &str, option_price: f64, profit_percent: f64, iv: f64) -> OptionChain {
+        OptionChain {
+            profit_percent,
+            implied_volatility: iv,
+            contract_id: contract_id.to_string(),
+            ..OptionChain::sample(OptionType::Put, 500.0, option_price)
+        }
+    }
+
+    #[test]
+    fn test_new_gone_and_changed() {
+        let previous = [
+            option("1", 4.00, 3.0, 0.30),
+            option("2", 2.00, 5.0, 0.40),
+            option("3", 1.00, 2.0, 0.50),
+        ];
+        let current = [
+            option("2", 2.05, 5.5, 0.41), // small moves only
+            option("3", 1.00, 2.0, 0.53), // IV up 3 points
+            option("4", 3.00, 6.0, 0.35),
+        ];
+
+        let changes = diff_options(&previous, &current, &CycleDiffConfig::default());
+        let summary: Vec<(ChangeKind, &str)> = changes
+            .iter()
+            .map(|c| (c.change, c.contract_id.as_str()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Changed, "3"),
+                (ChangeKind::New, "4"),
+                (ChangeKind::Gone, "1"),
+            ]
+        );
+
+        let changed = &changes[0];
+        assert!((changed.previous_iv.unwrap() - 50.0).abs() < 1e-9);
+        assert!((changed.current_iv.unwrap() - 53.0).abs() < 1e-9);
+        assert_eq!(changes[1].previous_price, None);
+        assert_eq!(changes[2].current_profit, None);
+    }
+
+    #[test]
+    fn test_zero_thresholds_report_any_move() {
+        let previous = [option("1", 2.00, 5.0, 0.40), option("2", 2.00, 5.0, 0.40)];
+        let current = [option("1", 2.00, 5.0, 0.40), option("2", 2.01, 5.0, 0.40)];
+        let thresholds = CycleDiffConfig {
+            min_price_change_percent: 0.0,
+            min_profit_change: 0.0,
+            min_iv_change: 0.0,
+            ..CycleDiffConfig::default()
+        };
+
+        let changes = diff_options(&previous, &current, &thresholds);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].contract_id, "2");
+    }
+
+    fn candidate(strategy: &str, legs: &[&str], score: f64) -> Candidate {
+        Candidate {
+            strategy: strategy.to_string(),
+            symbol: "META".to_string(),
+            score,
+            contract_ids: legs.iter().map(|leg| leg.to_string()).collect(),
+            ..Candidate::default()
+        }
+    }
+
+    #[test]
+    fn test_candidates_keyed_by_strategy_and_legs() {
+        let previous = [
+            candidate("vertical_spreads", &["1", "2"], 1.50),
+            candidate("vertical_spreads", &["2", "1"], 0.60),
+            candidate("covered_calls", &["2"], 20.0),
+        ];
+        let current = [
+            candidate("vertical_spreads", &["1", "2"], 1.53), // 2%, below the 5% threshold
+            candidate("vertical_spreads", &["2", "1"], 0.70),
+            candidate("cash_secured_puts", &["2"], 20.0),
+        ];
+
+        let changes = diff_candidates(&previous, &current, &CycleDiffConfig::default());
+        let summary: Vec<(ChangeKind, &str, &str)> = changes
+            .iter()
+            .map(|c| (c.change, c.strategy.as_str(), c.contract_ids.as_str()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Changed, "vertical_spreads", "2+1"),
+                (ChangeKind::New, "cash_secured_puts", "2"),
+                (ChangeKind::Gone, "covered_calls", "2"),
+            ]
+        );
+        assert_eq!(changes[0].previous_score, Some(0.60));
+        assert_eq!(changes[0].current_score, Some(0.70));
+    }
+}
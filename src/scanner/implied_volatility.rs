@@ -0,0 +1,156 @@
+use crate::models::OptionType;
+use crate::scanner::pricing::{black_scholes, PricingInputs};
+use thiserror::Error;
+
+const MIN_VOLATILITY: f64 = 1e-4;
+const MAX_VOLATILITY: f64 = 5.0;
+const PRICE_TOLERANCE: f64 = 1e-6;
+const VOLATILITY_TOLERANCE: f64 = 1e-8;
+const MAX_ITERATIONS: usize = 100;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum IvError {
+    #[error("no usable price")]
+    NoPrice,
+    #[error("contract has expired")]
+    Expired,
+    #[error("price {price:.4} is below the no-arbitrage floor {floor:.4}")]
+    BelowIntrinsic { price: f64, floor: f64 },
+    #[error("price {price:.4} is above the no-arbitrage ceiling {ceiling:.4}")]
+    AboveMaximum { price: f64, ceiling: f64 },
+    #[error("solver did not converge after {0} iterations")]
+    NoConvergence(usize),
+}
+
+// Solves Black-Scholes for volatility. Newton-Raphson on vega, with every step kept inside
+// a shrinking bisection bracket so flat-vega regions (deep ITM/OTM, near expiry) still converge.
+// `inputs.volatility` is ignored.
+pub fn implied_volatility(
+    option_type: &OptionType,
+    price: f64,
+    inputs: &PricingInputs,
+) -> Result<f64, IvError> {
+    if !price.is_finite() || price <= 0.0 || inputs.spot <= 0.0 || inputs.strike <= 0.0 {
+        return Err(IvError::NoPrice);
+    }
+    if inputs.time_to_expiry <= 0.0 {
+        return Err(IvError::Expired);
+    }
+
+    let value_at = |volatility: f64| {
+        black_scholes(option_type, &PricingInputs { volatility, ..*inputs })
+    };
+
+    let floor = value_at(0.0).value;
+    let ceiling = value_at(MAX_VOLATILITY).value;
+
+    if price < floor - PRICE_TOLERANCE {
+        return Err(IvError::BelowIntrinsic { price, floor });
+    }
+    if price > ceiling + PRICE_TOLERANCE {
+        return Err(IvError::AboveMaximum { price, ceiling });
+    }
+
+    let mut low = MIN_VOLATILITY;
+    let mut high = MAX_VOLATILITY;
+
+    // Brenner-Subrahmanyam approximation as the starting point
+    let mut volatility = ((2.0 * std::f64::consts::PI / inputs.time_to_expiry).sqrt() * price
+        / inputs.spot)
+        .clamp(0.05, 2.0);
+
+    for _ in 0..MAX_ITERATIONS {
+        let greeks = value_at(volatility);
+        let diff = greeks.value - price;
+
+        if diff.abs() < PRICE_TOLERANCE {
+            return Ok(volatility);
+        }
+
+        // Value rises with volatility, so the sign of diff tells which side the root is on
+        if diff > 0.0 {
+            high = volatility;
+        } else {
+            low = volatility;
+        }
+
+        let vega = greeks.vega * 100.0; // per unit of volatility
+        let newton = volatility - diff / vega;
+
+        let next = if vega > 1e-10 && newton > low && newton < high {
+            newton
+        } else {
+            0.5 * (low + high)
+        };
+
+        if (next - volatility).abs() < VOLATILITY_TOLERANCE || high - low < VOLATILITY_TOLERANCE {
+            return Ok(next);
+        }
+
+        volatility = next;
+    }
+
+    Err(IvError::NoConvergence(MAX_ITERATIONS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(spot: f64, strike: f64, time_to_expiry: f64) -> PricingInputs {
+        PricingInputs {
+            spot,
+            strike,
+            time_to_expiry,
+            rate: 0.045,
+            dividend_yield: 0.01,
+            volatility: 0.0,
+        }
+    }
+
+    fn round_trip(option_type: OptionType, inputs: PricingInputs, volatility: f64) {
+        let price = black_scholes(&option_type, &PricingInputs { volatility, ..inputs }).value;
+        let solved = implied_volatility(&option_type, price, &inputs).unwrap();
+        assert!(
+            (solved - volatility).abs() < 1e-4,
+            "{:?} {:?}: expected {}, got {}",
+            option_type,
+            inputs,
+            volatility,
+            solved
+        );
+    }
+
+    #[test]
+    fn test_recovers_volatility_across_moneyness() {
+        for option_type in [OptionType::Call, OptionType::Put] {
+            round_trip(option_type.clone(), inputs(100.0, 100.0, 0.25), 0.30);
+            round_trip(option_type.clone(), inputs(100.0, 70.0, 0.5), 0.45);
+            round_trip(option_type.clone(), inputs(100.0, 130.0, 0.5), 0.35);
+            round_trip(option_type.clone(), inputs(100.0, 101.0, 2.0 / 365.0), 0.60);
+            round_trip(option_type, inputs(100.0, 100.0, 1.0), 1.50);
+        }
+    }
+
+    #[test]
+    fn test_rejects_prices_outside_arbitrage_bounds() {
+        let deep_itm = inputs(150.0, 100.0, 0.1);
+
+        assert!(matches!(
+            implied_volatility(&OptionType::Call, 40.0, &deep_itm),
+            Err(IvError::BelowIntrinsic { .. })
+        ));
+        assert!(matches!(
+            implied_volatility(&OptionType::Call, 200.0, &deep_itm),
+            Err(IvError::AboveMaximum { .. })
+        ));
+        assert_eq!(
+            implied_volatility(&OptionType::Put, 0.0, &deep_itm),
+            Err(IvError::NoPrice)
+        );
+        assert_eq!(
+            implied_volatility(&OptionType::Put, 1.0, &inputs(100.0, 100.0, 0.0)),
+            Err(IvError::Expired)
+        );
+    }
+}
@@ -1,6 +1,13 @@
 use crate::models::OptionChain;
+use crate::scanner::calendar_spread::CalendarSpread;
+use crate::scanner::cash_secured_put::CashSecuredPut;
+use crate::scanner::covered_call::CoveredCall;
+use crate::scanner::cycle_diff::{CandidateChange, OpportunityChange};
+use crate::scanner::neutral_strategies::NeutralSetup;
+use crate::scanner::strategy::Candidate;
+use crate::scanner::vertical_spread::VerticalSpread;
 use chrono::{DateTime, Utc};
-use csv::Writer;
+use csv::WriterBuilder;
 use serde::Serialize;
 use std::fs::{self, File, OpenOptions};
 use std::io::Write;
@@ -20,15 +27,51 @@ struct OptionLogEntry {
     timestamp: DateTime<Utc>,
     symbol: String,
     option_type: String,
+    type_emoji: String,
     expiration: DateTime<Utc>,
     strike: f64,
     option_price: f64,
+    bid: f64,
+    ask: f64,
+    last: f64,
+    mark: f64,
     stock_price: f64,
     profit_percent: f64,
+    trading_cost: f64,
+    breakeven: f64,
+    max_gain: Option<f64>,
+    max_loss: f64,
+    return_on_capital: f64,
+    annualized_return: f64,
+    days_to_expiry: f64,
     volume: i32,
-    implied_volatility: f64,
+    open_interest: i32,
+    implied_volatility: Option<f64>,
+    iv_status: String,
+    theoretical_value: f64,
+    early_exercise_premium: f64,
+    delta: f64,
+    gamma: f64,
+    theta: f64,
+    vega: f64,
+    rho: f64,
+    prob_itm: f64,
+    prob_profit: f64,
+    prob_touch: f64,
+    expected_value: f64,
+    score: Option<f64>,
     contract_id: String,
-    type_emoji: String,
+}
+
+// Legs and metrics flattened so any strategy fits the same columns
+#[derive(Debug, Serialize)]
+struct CandidateLogEntry {
+    timestamp: DateTime<Utc>,
+    strategy: String,
+    symbol: String,
+    score: f64,
+    contract_ids: String,
+    metrics: String,
 }
 
 pub struct OptionLogger {
@@ -53,8 +96,150 @@ impl OptionLogger {
         }
         
         let timestamp = Utc::now();
-        let date_str = timestamp.format("%Y%m%d").to_string();
-        let filename = format!("options_{}.csv", date_str);
+        let entries = options.iter().map(|option| OptionLogEntry {
+            timestamp: option.timestamp,
+            symbol: option.symbol.clone(),
+            option_type: option.option_type.to_string(),
+            type_emoji: option.option_type.emoji().to_string(),
+            expiration: option.expiration,
+            strike: option.strike,
+            option_price: option.option_price,
+            bid: option.bid,
+            ask: option.ask,
+            last: option.last,
+            mark: option.mark,
+            stock_price: option.stock_price,
+            profit_percent: option.profit_percent,
+            trading_cost: option.trading_cost,
+            breakeven: option.metrics.breakeven,
+            // Blank for unlimited upside
+            max_gain: option.metrics.max_gain,
+            max_loss: option.metrics.max_loss,
+            return_on_capital: option.metrics.return_on_capital,
+            annualized_return: option.metrics.annualized_return,
+            days_to_expiry: option.metrics.days_to_expiry,
+            volume: option.volume,
+            open_interest: option.open_interest,
+            // Left blank rather than logging a made-up number when the solver failed
+            implied_volatility: option
+                .iv_status
+                .is_usable()
+                .then_some(option.implied_volatility),
+            iv_status: option.iv_status.to_string(),
+            theoretical_value: option.theoretical_value,
+            early_exercise_premium: option.early_exercise_premium,
+            delta: option.delta,
+            gamma: option.gamma,
+            theta: option.theta,
+            vega: option.vega,
+            rho: option.rho,
+            prob_itm: option.prob_itm,
+            prob_profit: option.prob_profit,
+            prob_touch: option.prob_touch,
+            expected_value: option.expected_value,
+            score: option.score,
+            contract_id: option.contract_id.clone(),
+        });
+        
+        self.append_csv("options", timestamp, entries)?;
+        
+        // Also create a summary log for today
+        self.create_summary_log(options, timestamp)?;
+        
+        Ok(())
+    }
+    
+    pub fn log_covered_calls(&self, covered_calls: &[&CoveredCall]) -> Result<(), LoggerError> {
+        if covered_calls.is_empty() {
+            return Ok(());
+        }
+        
+        self.append_csv("covered_calls", Utc::now(), covered_calls)
+    }
+    
+    pub fn log_cash_secured_puts(&self, puts: &[&CashSecuredPut]) -> Result<(), LoggerError> {
+        if puts.is_empty() {
+            return Ok(());
+        }
+        
+        self.append_csv("cash_secured_puts", Utc::now(), puts)
+    }
+    
+    pub fn log_vertical_spreads(&self, spreads: &[&VerticalSpread]) -> Result<(), LoggerError> {
+        if spreads.is_empty() {
+            return Ok(());
+        }
+        
+        self.append_csv("vertical_spreads", Utc::now(), spreads)
+    }
+    
+    pub fn log_neutral_setups(&self, setups: &[&NeutralSetup]) -> Result<(), LoggerError> {
+        if setups.is_empty() {
+            return Ok(());
+        }
+        
+        self.append_csv("neutral_strategies", Utc::now(), setups)
+    }
+    
+    pub fn log_calendar_spreads(&self, spreads: &[&CalendarSpread]) -> Result<(), LoggerError> {
+        if spreads.is_empty() {
+            return Ok(());
+        }
+        
+        self.append_csv("calendar_spreads", Utc::now(), spreads)
+    }
+    
+    pub fn log_candidates(&self, candidates: &[&Candidate]) -> Result<(), LoggerError> {
+        if candidates.is_empty() {
+            return Ok(());
+        }
+        
+        let entries: Vec<CandidateLogEntry> = candidates
+            .iter()
+            .map(|candidate| CandidateLogEntry {
+                timestamp: candidate.timestamp,
+                strategy: candidate.strategy.clone(),
+                symbol: candidate.symbol.clone(),
+                score: candidate.score,
+                contract_ids: candidate.contract_ids.join("+"),
+                metrics: candidate
+                    .metrics
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect::<Vec<_>>()
+                    .join(";"),
+            })
+            .collect();
+        
+        self.append_csv("strategy_candidates", Utc::now(), &entries)
+    }
+    
+    // Separate from the options log so each cycle's changes can be read on their own
+    pub fn log_changes(&self, changes: &[OpportunityChange]) -> Result<(), LoggerError> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        
+        self.append_csv("option_changes", Utc::now(), changes)
+    }
+    
+    pub fn log_candidate_changes(&self, changes: &[CandidateChange]) -> Result<(), LoggerError> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        
+        self.append_csv("strategy_changes", Utc::now(), changes)
+    }
+    
+    // Appends rows to <prefix>_YYYYMMDD.csv; the header comes from the row type and is only
+    // written when the file is new
+    fn append_csv<T: Serialize>(
+        &self,
+        prefix: &str,
+        timestamp: DateTime<Utc>,
+        rows: impl IntoIterator<Item = T>,
+    ) -> Result<(), LoggerError> {
+        let filename = format!("{}_{}.csv", prefix, timestamp.format("%Y%m%d"));
         let filepath = self.log_dir.join(filename);
         
         let file_exists = filepath.exists();
@@ -64,50 +249,15 @@ impl OptionLogger {
             .append(true)
             .open(&filepath)?;
         
-        let mut wtr = Writer::from_writer(file);
-        
-        // Write header if file is new
-        if !file_exists {
-            wtr.write_record(&[
-                "timestamp",
-                "symbol",
-                "option_type",
-                "type_emoji",
-                "expiration",
-                "strike",
-                "option_price",
-                "stock_price",
-                "profit_percent",
-                "volume",
-                "implied_volatility",
-                "contract_id",
-            ])?;
-        }
+        let mut wtr = WriterBuilder::new()
+            .has_headers(!file_exists)
+            .from_writer(file);
         
-        for option in options {
-            let entry = OptionLogEntry {
-                timestamp: option.timestamp,
-                symbol: option.symbol.clone(),
-                option_type: option.option_type.to_string(),
-                expiration: option.expiration,
-                strike: option.strike,
-                option_price: option.option_price,
-                stock_price: option.stock_price,
-                profit_percent: option.profit_percent,
-                volume: option.volume,
-                implied_volatility: option.implied_volatility,
-                contract_id: option.contract_id.clone(),
-                type_emoji: option.option_type.emoji().to_string(),
-            };
-            
-            wtr.serialize(entry)?;
+        for row in rows {
+            wtr.serialize(row)?;
         }
         
         wtr.flush()?;
-        
-        // Also create a summary log for today
-        self.create_summary_log(options, timestamp)?;
-        
         Ok(())
     }
     
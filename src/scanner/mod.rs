@@ -1,6 +1,18 @@
 pub mod nasdaq_scanner;
+pub mod american_pricing;
+pub mod calendar_spread;
+pub mod cash_secured_put;
+pub mod covered_call;
+pub mod cycle_diff;
+pub mod implied_volatility;
+pub mod neutral_strategies;
+pub mod pricing;
+pub mod probability;
 pub mod profit_calculator;
+pub mod ranking;
+pub mod strategy;
+pub mod vertical_spread;
 pub mod logger;
 
-// Re-export the run function so it can be called as scanner::run()
-pub use nasdaq_scanner::run;
+// Re-export the entry points so they can be called as scanner::run()
+pub use nasdaq_scanner::{check_connection, load_watchlist, run, run_once};
@@ -1,9 +1,37 @@
-use crate::api::IbkrClient;
-use crate::models::{OptionChain, ScannerConfig, StockList};
-use crate::scanner::profit_calculator::calculate_profit_percent;
+use crate::api::{
+    ConidCache, IbkrClient, RateLimiter, RetryPolicy, SessionMonitor, SessionState, UnderlyingQuote,
+};
+use crate::models::{
+    IvStatus, OptionChain, OptionChainFilters, OptionType, ScannerConfig, Watchlist,
+    WatchlistEntry, WatchlistError,
+};
+use crate::scanner::american_pricing::{
+    american_implied_volatility, american_price, escrowed_inputs, project_dividends, Dividend,
+};
+use crate::scanner::calendar_spread::CalendarSpread;
+use crate::scanner::cash_secured_put::CashSecuredPut;
+use crate::scanner::covered_call::CoveredCall;
+use crate::scanner::cycle_diff::{
+    diff_candidates, diff_options, CandidateChange, ChangeKind, OpportunityChange,
+};
+use crate::scanner::neutral_strategies::NeutralSetup;
+use crate::scanner::pricing::{black_scholes, years_to_expiry, PricingInputs};
+use crate::scanner::probability::probability_metrics;
+use crate::scanner::profit_calculator::{calculate_metrics, calculate_profit_percent};
 use crate::scanner::logger::{OptionLogger, LoggerError};
-use log::{error, info, warn};
-use std::sync::Arc;
+use crate::scanner::ranking::{apply_limits, rank_candidates, rank_options};
+use crate::scanner::strategy::{
+    Candidate, CandidateDetail, StrategyInput, StrategyRegistry, CALENDAR_SPREADS,
+    CASH_SECURED_PUTS, COVERED_CALLS, LONG_OPTIONS, NEUTRAL_STRATEGIES, VERTICAL_SPREADS,
+};
+use crate::scanner::vertical_spread::VerticalSpread;
+use chrono::{DateTime, Utc};
+use log::{debug, error, info, warn};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use tokio::time::{sleep, Duration};
 use thiserror::Error;
 
@@ -17,50 +45,161 @@ pub enum ScannerError {
     ConfigError(String),
     #[error("Logging error: {0}")]
     LoggerError(#[from] LoggerError),
+    #[error("Watchlist error: {0}")]
+    WatchlistError(#[from] WatchlistError),
+    #[error("Gateway session unavailable: {0}")]
+    SessionUnavailable(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct SymbolScanTiming {
+    pub symbol: String,
+    pub elapsed: Duration,
+    pub options_found: usize,
+    pub error: Option<String>,
+}
+
+// Everything one cycle found, in watchlist order. Long options keep their detailed table;
+// every other strategy's ideas are candidates, with typed views for the built-in ones.
+#[derive(Debug, Clone, Default)]
+pub struct ScanResults {
+    pub options: Vec<OptionChain>,
+    pub candidates: Vec<Candidate>,
+    pub changes: Vec<OpportunityChange>, // against the previous cycle, not per symbol
+    pub candidate_changes: Vec<CandidateChange>,
+}
+
+impl ScanResults {
+    // Routed by what the candidate carries, not by which strategy produced it
+    fn push(&mut self, candidate: Candidate) {
+        match candidate.detail {
+            CandidateDetail::LongOption(chain) => self.options.push(*chain),
+            _ => self.candidates.push(candidate),
+        }
+    }
+    
+    fn append(&mut self, other: &mut ScanResults) {
+        self.options.append(&mut other.options);
+        self.candidates.append(&mut other.candidates);
+    }
+    
+    fn retain_symbols(&mut self, keep: impl Fn(&str) -> bool) {
+        self.options.retain(|option| keep(&option.symbol));
+        self.candidates.retain(|candidate| keep(&candidate.symbol));
+    }
+    
+    pub fn covered_calls(&self) -> Vec<&CoveredCall> {
+        self.details(|detail| match detail {
+            CandidateDetail::CoveredCall(call) => Some(call),
+            _ => None,
+        })
+    }
+    
+    pub fn cash_secured_puts(&self) -> Vec<&CashSecuredPut> {
+        self.details(|detail| match detail {
+            CandidateDetail::CashSecuredPut(put) => Some(put),
+            _ => None,
+        })
+    }
+    
+    pub fn vertical_spreads(&self) -> Vec<&VerticalSpread> {
+        self.details(|detail| match detail {
+            CandidateDetail::VerticalSpread(spread) => Some(spread),
+            _ => None,
+        })
+    }
+    
+    pub fn neutral_setups(&self) -> Vec<&NeutralSetup> {
+        self.details(|detail| match detail {
+            CandidateDetail::Neutral(setup) => Some(setup),
+            _ => None,
+        })
+    }
+    
+    pub fn calendar_spreads(&self) -> Vec<&CalendarSpread> {
+        self.details(|detail| match detail {
+            CandidateDetail::Calendar(spread) => Some(spread),
+            _ => None,
+        })
+    }
+    
+    // Candidates from strategies without a table of their own
+    pub fn generic_candidates(&self) -> Vec<&Candidate> {
+        self.candidates
+            .iter()
+            .filter(|candidate| matches!(candidate.detail, CandidateDetail::Generic))
+            .collect()
+    }
+    
+    fn details<'a, T>(&'a self, pick: impl Fn(&'a CandidateDetail) -> Option<&'a T>) -> Vec<&'a T> {
+        self.candidates.iter().filter_map(|candidate| pick(&candidate.detail)).collect()
+    }
 }
 
 pub struct NasdaqScanner {
     client: Arc<IbkrClient>,
     config: ScannerConfig,
+    watchlist: Watchlist,
     logger: OptionLogger,
+    strategies: StrategyRegistry,
+    // Last cycle's unranked results, the baseline for the next diff
+    previous: Mutex<Option<ScanResults>>,
 }
 
 impl NasdaqScanner {
     pub fn new(client: IbkrClient, config: ScannerConfig) -> Result<Self, ScannerError> {
+        let strategies = StrategyRegistry::builtin(&config);
+        Self::with_strategies(client, config, strategies)
+    }
+    
+    // Runs the strategies named in config.strategies, looked up in `strategies`
+    pub fn with_strategies(
+        client: IbkrClient,
+        config: ScannerConfig,
+        strategies: StrategyRegistry,
+    ) -> Result<Self, ScannerError> {
         let logger = OptionLogger::new(&config.options_log_dir)?;
+        let watchlist = load_watchlist(&config)?;
+        
+        let available = strategies.names().join(", ");
+        let strategies = strategies.select(&config.strategies).map_err(|name| {
+            ScannerError::ConfigError(format!(
+                "Unknown strategy '{}' (available: {})",
+                name, available
+            ))
+        })?;
+        
+        info!("Watchlist {} with {} symbols", watchlist.name, watchlist.entries.len());
         
         Ok(Self {
             client: Arc::new(client),
             config,
+            watchlist,
             logger,
+            strategies,
+            previous: Mutex::new(None),
         })
     }
     
-    pub async fn run_scanner(&self) -> Result<(), ScannerError> {
+    pub async fn run_scanner(self: Arc<Self>) -> Result<(), ScannerError> {
         info!("Starting NASDAQ option scanner");
         
-        // Test connection first
-        if !self.client.test_connection().await? {
-            return Err(ScannerError::ConfigError(
-                "Failed to connect to IBKR Gateway".to_string(),
-            ));
-        }
+        self.ensure_connected().await?;
+        
+        // Keep the brokerage session alive between scan cycles
+        let mut session = SessionMonitor::spawn(
+            Arc::clone(&self.client),
+            Duration::from_secs(self.config.tickle_interval_seconds),
+        );
         
         loop {
-            info!("Starting scan cycle");
-            
-            let stocks = StockList::from_symbols(&self.config.nasdaq_stocks);
-            let profitable_options = self.scan_stocks(&stocks).await?;
+            session.wait_until_ready().await;
             
-            // Log profitable options
-            self.logger.log_options(&profitable_options)?;
-            
-            // Display results
-            self.display_results(&profitable_options);
+            let results = self.run_cycle().await?;
             
             info!(
                 "Scan complete. Found {} profitable options. Next scan in {} seconds",
-                profitable_options.len(),
+                results.options.len(),
                 self.config.scan_interval_seconds
             );
             
@@ -68,76 +207,327 @@ impl NasdaqScanner {
         }
     }
     
+    // Single scan cycle for cron/scripts; fails fast if the session isn't usable
+    pub async fn scan_once(self: Arc<Self>) -> Result<ScanResults, ScannerError> {
+        self.ensure_connected().await?;
+        
+        let state = self.client.auth_status().await?.session_state();
+        if !state.is_ready() {
+            return Err(ScannerError::SessionUnavailable(state.to_string()));
+        }
+        
+        self.run_cycle().await
+    }
+    
+    async fn ensure_connected(&self) -> Result<(), ScannerError> {
+        if !self.client.test_connection().await? {
+            return Err(ScannerError::ConfigError(
+                "Failed to connect to IBKR Gateway".to_string(),
+            ));
+        }
+        
+        Ok(())
+    }
+    
+    async fn run_cycle(self: &Arc<Self>) -> Result<ScanResults, ScannerError> {
+        info!("Starting scan cycle");
+        
+        let (mut results, timings) = self.scan_stocks(&self.watchlist).await;
+        
+        // Diff the full per-symbol results before ranking rescales scores or the limits trim
+        let baseline = if self.config.cycle_diff.enabled {
+            let failed: HashSet<&str> = timings
+                .iter()
+                .filter(|timing| timing.error.is_some())
+                .map(|timing| timing.symbol.as_str())
+                .collect();
+            let mut previous = self.previous.lock().expect("previous results lock poisoned");
+            let (baseline, snapshot) = next_baseline(previous.take(), &results, &failed);
+            *previous = Some(snapshot);
+            baseline
+        } else {
+            None
+        };
+        if let Some(previous) = &baseline {
+            let thresholds = &self.config.cycle_diff;
+            results.changes = diff_options(&previous.options, &results.options, thresholds);
+            results.candidate_changes =
+                diff_candidates(&previous.candidates, &results.candidates, thresholds);
+        }
+        
+        if self.config.ranking.enabled {
+            let ranking = &self.config.ranking;
+            rank_options(&mut results.options, ranking);
+            rank_candidates(&mut results.candidates);
+            apply_limits(&mut results.options, ranking, |option| ("", option.symbol.as_str()));
+            apply_limits(&mut results.candidates, ranking, |candidate| {
+                (candidate.strategy.as_str(), candidate.symbol.as_str())
+            });
+        }
+        
+        // Log profitable options and strategy candidates
+        self.logger.log_options(&results.options)?;
+        self.logger.log_covered_calls(&results.covered_calls())?;
+        self.logger.log_cash_secured_puts(&results.cash_secured_puts())?;
+        self.logger.log_vertical_spreads(&results.vertical_spreads())?;
+        self.logger.log_neutral_setups(&results.neutral_setups())?;
+        self.logger.log_calendar_spreads(&results.calendar_spreads())?;
+        self.logger.log_candidates(&results.generic_candidates())?;
+        self.logger.log_changes(&results.changes)?;
+        self.logger.log_candidate_changes(&results.candidate_changes)?;
+        
+        // Display results for the strategies that ran
+        if self.strategies.contains(LONG_OPTIONS) {
+            self.display_results(&results.options);
+        }
+        if self.strategies.contains(COVERED_CALLS) {
+            self.display_covered_calls(&results.covered_calls());
+        }
+        if self.strategies.contains(CASH_SECURED_PUTS) {
+            self.display_cash_secured_puts(&results.cash_secured_puts());
+        }
+        if self.strategies.contains(VERTICAL_SPREADS) {
+            self.display_vertical_spreads(&results.vertical_spreads());
+        }
+        if self.strategies.contains(NEUTRAL_STRATEGIES) {
+            self.display_neutral_setups(&results.neutral_setups());
+        }
+        if self.strategies.contains(CALENDAR_SPREADS) {
+            self.display_calendar_spreads(&results.calendar_spreads());
+        }
+        let generic = results.generic_candidates();
+        if !generic.is_empty() {
+            self.display_candidates(&generic);
+        }
+        // The first cycle has nothing to compare against
+        if baseline.is_some() {
+            self.display_changes(&results.changes);
+            self.display_candidate_changes(&results.candidate_changes);
+        }
+        self.display_timings(&timings);
+        
+        Ok(results)
+    }
+    
+    // Scans symbols concurrently (bounded by max_concurrent_symbols), results in watchlist order
     async fn scan_stocks(
-        &self,
-        stocks: &StockList,
-    ) -> Result<Vec<OptionChain>, ScannerError> {
-        let mut all_profitable_options = Vec::new();
+        self: &Arc<Self>,
+        watchlist: &Watchlist,
+    ) -> (ScanResults, Vec<SymbolScanTiming>) {
+        let limit = Arc::new(Semaphore::new(self.config.max_concurrent_symbols.max(1)));
+        let mut tasks = JoinSet::new();
         
-        for stock in &stocks.stocks {
-            info!("Scanning {}", stock.symbol);
+        for (index, stock) in watchlist.entries.iter().cloned().enumerate() {
+            let scanner = Arc::clone(self);
+            let limit = Arc::clone(&limit);
             
-            match self.scan_single_stock(stock).await {
-                Ok(mut options) => {
-                    all_profitable_options.append(&mut options);
+            tasks.spawn(async move {
+                let _permit = limit.acquire_owned().await.expect("scan semaphore closed");
+                
+                info!("Scanning {}", stock.symbol);
+                let started = Instant::now();
+                let result = scanner.scan_single_stock(&stock).await;
+                
+                (index, stock.symbol, started.elapsed(), result)
+            });
+        }
+        
+        let mut results = Vec::with_capacity(watchlist.entries.len());
+        
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(result) => results.push(result),
+                Err(e) => error!("Scan task panicked: {}", e),
+            }
+        }
+        
+        results.sort_by_key(|(index, ..)| *index);
+        
+        let mut all_results = ScanResults::default();
+        let mut timings = Vec::with_capacity(results.len());
+        
+        for (_, symbol, elapsed, result) in results {
+            let (options_found, error) = match result {
+                Ok(mut symbol_results) => {
+                    let found = symbol_results.options.len();
+                    all_results.append(&mut symbol_results);
+                    (found, None)
                 }
                 Err(e) => {
-                    error!("Failed to scan {}: {}", stock.symbol, e);
+                    error!("Failed to scan {}: {}", symbol, e);
+                    (0, Some(e.to_string()))
                 }
-            }
+            };
+            
+            timings.push(SymbolScanTiming {
+                symbol,
+                elapsed,
+                options_found,
+                error,
+            });
         }
         
-        info!("Scan complete. Found {} profitable options", all_profitable_options.len());
+        info!("Scan complete. Found {} profitable options", all_results.options.len());
         
-        Ok(all_profitable_options)
+        (all_results, timings)
     }
     
     async fn scan_single_stock(
         &self,
-        stock: &crate::models::Stock,
-    ) -> Result<Vec<OptionChain>, ScannerError> {
-        let mut profitable_options = Vec::new();
+        stock: &WatchlistEntry,
+    ) -> Result<ScanResults, ScannerError> {
+        let rights = stock.rights.clone().unwrap_or_else(|| vec![OptionType::Call, OptionType::Put]);
         
         // Get stock price
-        let stock_price = match self.client.get_stock_price(&stock.symbol).await {
-            Ok(price) => price,
-            Err(e) => {
-                warn!("Failed to get price for {}: {}", stock.symbol, e);
-                return Ok(profitable_options);
-            }
-        };
+        let quote = self.client.get_underlying_quote(&stock.symbol).await?;
+        let stock_price = quote.price;
         
-        // Get option chain (using a reasonable number of strikes)
-        let option_chains = match self.client.get_option_chain(&stock.symbol, 20).await {
-            Ok(chains) => chains,
-            Err(e) => {
-                warn!("Failed to get option chain for {}: {}", stock.symbol, e);
-                return Ok(profitable_options);
-            }
-        };
+        // Get option chain for the strikes closest to the current price
+        let mut option_chains = self.client.get_option_chain(
+            &stock.symbol,
+            stock_price,
+            stock.strike_count.unwrap_or(self.config.strike_count),
+            self.config.option_months,
+            &rights,
+        ).await?;
+        
+        // Get bid/ask/last, volume, open interest and IV for every contract
+        self.client.populate_quotes(&mut option_chains).await?;
+        
+        // Calculate theoretical value, Greeks and profit for each option
+        let now = Utc::now();
+        let dividends = dividend_schedule(&quote, &option_chains, now);
         
-        // Calculate profit for each option
-        for mut chain in option_chains {
+        for chain in option_chains.iter_mut() {
             chain.stock_price = stock_price;
+            chain.trading_cost = self.config.fees.round_trip_cost(
+                chain.option_price,
+                chain.bid,
+                chain.ask,
+                chain.multiplier,
+            );
+            self.price_option(chain, now, &dividends);
+            
+            // Profit and return figures are net of trading costs
             chain.profit_percent = calculate_profit_percent(
                 chain.strike,
-                chain.option_price,
+                chain.cost_basis(),
+                chain.stock_price,
+                &chain.option_type,
+            );
+            chain.metrics = calculate_metrics(
+                chain.strike,
+                chain.cost_basis(),
                 chain.stock_price,
+                chain.multiplier,
+                years_to_expiry(chain.expiration, now) * 365.0,
+                chain.implied_volatility,
                 &chain.option_type,
             );
+        }
+        
+        // Global filters with any per-symbol overrides, for the strategies that use them
+        let filters = self.config.filters.with_overrides(&stock.filters);
+        let filtered = filter_contracts(&option_chains, &filters);
+        
+        debug!(
+            "{}: {} of {} contracts passed the filters",
+            stock.symbol,
+            filtered.len(),
+            option_chains.len()
+        );
+        
+        let mut results = ScanResults::default();
+        
+        for strategy in self.strategies.iter() {
+            let input = StrategyInput {
+                quote: &quote,
+                entry: stock,
+                chains: if strategy.uses_contract_filters() { &filtered } else { &option_chains },
+            };
             
-            if chain.profit_percent >= self.config.min_profit_percent {
-                profitable_options.push(chain);
+            for candidate in strategy.evaluate(&input) {
+                results.push(candidate);
             }
         }
         
         info!(
-            "Scanned {}: found {} profitable options",
+            "Scanned {}: found {} profitable options and {} strategy candidates",
             stock.symbol,
-            profitable_options.len()
+            results.options.len(),
+            results.candidates.len()
         );
         
-        Ok(profitable_options)
+        Ok(results)
+    }
+    
+    // Solves IV from the mid price against the American (tree) value, then fills the tree value,
+    // early exercise premium, Black-Scholes Greeks and lognormal probabilities. Discrete
+    // dividends, when known, replace DIVIDEND_YIELD in every model.
+    fn price_option(&self, chain: &mut OptionChain, now: DateTime<Utc>, dividends: &[Dividend]) {
+        let inputs = PricingInputs {
+            spot: chain.stock_price,
+            strike: chain.strike,
+            time_to_expiry: years_to_expiry(chain.expiration, now),
+            rate: self.config.risk_free_rate,
+            dividend_yield: self.config.dividend_yield,
+            volatility: 0.0,
+        };
+        
+        match american_implied_volatility(
+            &chain.option_type,
+            chain.mid_price(),
+            &inputs,
+            dividends,
+            self.config.tree_steps,
+        ) {
+            Ok(iv) => {
+                chain.implied_volatility = iv;
+                chain.iv_status = IvStatus::Solved;
+            }
+            Err(e) if chain.implied_volatility > 0.0 => {
+                debug!(
+                    "IV solve failed for {} ({}), using gateway IV",
+                    chain.contract_id, e
+                );
+                chain.iv_status = IvStatus::GatewayFallback;
+            }
+            Err(e) => {
+                warn!(
+                    "IV solve failed for {} {} {}: {}",
+                    chain.symbol, chain.strike, chain.option_type, e
+                );
+                chain.implied_volatility = 0.0;
+                chain.iv_status = IvStatus::Failed;
+            }
+        }
+        
+        let inputs = PricingInputs {
+            volatility: chain.implied_volatility,
+            ..inputs
+        };
+        
+        let american = american_price(&chain.option_type, &inputs, dividends, self.config.tree_steps);
+        let inputs = escrowed_inputs(&inputs, dividends);
+        let greeks = black_scholes(&chain.option_type, &inputs);
+        
+        chain.theoretical_value = american.value;
+        chain.early_exercise_premium = american.early_exercise_premium;
+        chain.delta = greeks.delta;
+        chain.gamma = greeks.gamma;
+        chain.theta = greeks.theta;
+        chain.vega = greeks.vega;
+        chain.rho = greeks.rho;
+        
+        // Probabilities need a volatility; leave them at zero rather than guess one
+        if chain.iv_status.is_usable() {
+            let odds = probability_metrics(&chain.option_type, chain.cost_basis(), &inputs);
+            
+            chain.prob_itm = odds.prob_itm;
+            chain.prob_profit = odds.prob_profit;
+            chain.prob_touch = odds.prob_touch;
+            chain.expected_value = odds.expected_value * chain.multiplier;
+        }
     }
     
     fn display_results(&self, options: &[OptionChain]) {
@@ -148,49 +538,670 @@ impl NasdaqScanner {
             return;
         }
         
+        // Ranked options arrive best first; an explicit sort expression still wins
+        let mut sorted = options.to_vec();
+        let order = match &self.config.sort_expression {
+            Some(sort) => {
+                sort.sort(&mut sorted);
+                sort.to_string()
+            }
+            None if self.config.ranking.enabled => "score".to_string(),
+            None => {
+                self.config.sort_by.sort(&mut sorted);
+                self.config.sort_by.to_string()
+            }
+        };
+        
         let mut table = Table::new();
         table.add_row(row![
             "Symbol",
             "Type",
             "Expiry",
             "Strike",
+            "Score",
             "Option Price",
+            "Bid/Ask",
             "Stock Price",
             "Profit %",
+            "Costs",
+            "Breakeven",
+            "Max Gain",
+            "Max Loss",
+            "RoC %",
+            "Ann. %",
+            "DTE",
             "Volume",
-            "IV %"
+            "OI",
+            "IV %",
+            "Theo",
+            "Early Ex",
+            "Delta",
+            "Gamma",
+            "Theta",
+            "Vega",
+            "Rho",
+            "P(ITM)",
+            "P(Profit)",
+            "P(Touch)",
+            "EV"
         ]);
         
-        for option in options {
+        for option in &sorted {
+            let metrics = &option.metrics;
             table.add_row(row![
                 option.symbol,
                 format!("{} {}", option.option_type.emoji(), option.option_type),
                 option.expiration.format("%Y-%m-%d"),
                 format!("${:.2}", option.strike),
+                option.score.map_or_else(|| "-".to_string(), |score| format!("{:.1}", score)),
                 format!("${:.2}", option.option_price),
+                format!("{:.2}/{:.2}", option.bid, option.ask),
                 format!("${:.2}", option.stock_price),
                 format!("{:.2}%", option.profit_percent),
+                format!("${:.2}", option.trading_cost),
+                format!("${:.2}", metrics.breakeven),
+                metrics
+                    .max_gain
+                    .map_or_else(|| "unlimited".to_string(), |gain| format!("${:.0}", gain)),
+                format!("${:.0}", metrics.max_loss),
+                format!("{:.1}%", metrics.return_on_capital),
+                format!("{:.1}%", metrics.annualized_return),
+                format!("{:.1}", metrics.days_to_expiry),
                 option.volume,
-                format!("{:.1}%", option.implied_volatility * 100.0)
+                option.open_interest,
+                if option.iv_status.is_usable() {
+                    format!("{:.1}%", option.implied_volatility * 100.0)
+                } else {
+                    "n/a".to_string()
+                },
+                format!("${:.2}", option.theoretical_value),
+                format!("${:.2}", option.early_exercise_premium),
+                format!("{:.3}", option.delta),
+                format!("{:.4}", option.gamma),
+                format!("{:.3}", option.theta),
+                format!("{:.3}", option.vega),
+                format!("{:.3}", option.rho),
+                format!("{:.1}%", option.prob_itm * 100.0),
+                format!("{:.1}%", option.prob_profit * 100.0),
+                format!("{:.1}%", option.prob_touch * 100.0),
+                format!("${:.2}", option.expected_value)
+            ]);
+        }
+        
+        println!("\nProfitable Options Found (by {}):", order);
+        table.printstd();
+    }
+    
+    fn display_covered_calls(&self, covered_calls: &[&CoveredCall]) {
+        use prettytable::{Table, row};
+        
+        if covered_calls.is_empty() {
+            println!("No covered calls met the thresholds.");
+            return;
+        }
+        
+        let mut table = Table::new();
+        table.add_row(row![
+            "Symbol",
+            "Expiry",
+            "Strike",
+            "Stock Price",
+            "Premium",
+            "Breakeven",
+            "Static %",
+            "If Called %",
+            "Protection %",
+            "Ann. %",
+            "DTE",
+            "Delta",
+            "P(Called)"
+        ]);
+        
+        for call in covered_calls {
+            table.add_row(row![
+                call.symbol,
+                call.expiration.format("%Y-%m-%d"),
+                format!("${:.2}", call.strike),
+                format!("${:.2}", call.stock_price),
+                format!("${:.2}", call.premium),
+                format!("${:.2}", call.breakeven),
+                format!("{:.2}%", call.static_return),
+                format!("{:.2}%", call.if_called_return),
+                format!("{:.2}%", call.downside_protection),
+                format!("{:.1}%", call.annualized_yield),
+                format!("{:.1}", call.days_to_expiry),
+                format!("{:.3}", call.delta),
+                format!("{:.1}%", call.prob_called * 100.0)
             ]);
         }
         
-        println!("\nProfitable Options Found:");
+        println!("\nCovered Calls:");
+        table.printstd();
+    }
+    
+    fn display_cash_secured_puts(&self, puts: &[&CashSecuredPut]) {
+        use prettytable::{Table, row};
+        
+        if puts.is_empty() {
+            println!("No cash-secured puts met the thresholds.");
+            return;
+        }
+        
+        let mut table = Table::new();
+        table.add_row(row![
+            "Symbol",
+            "Expiry",
+            "Strike",
+            "Stock Price",
+            "Premium",
+            "Collateral",
+            "Yield %",
+            "Effective Price",
+            "Below Spot %",
+            "Ann. %",
+            "DTE",
+            "Delta",
+            "P(Assigned)"
+        ]);
+        
+        for put in puts {
+            table.add_row(row![
+                put.symbol,
+                put.expiration.format("%Y-%m-%d"),
+                format!("${:.2}", put.strike),
+                format!("${:.2}", put.stock_price),
+                format!("${:.2}", put.premium),
+                format!("${:.0}", put.collateral),
+                format!("{:.2}%", put.premium_yield),
+                format!("${:.2}", put.effective_purchase_price),
+                format!("{:.2}%", put.distance_from_spot),
+                format!("{:.1}%", put.annualized_return),
+                format!("{:.1}", put.days_to_expiry),
+                format!("{:.3}", put.delta),
+                format!("{:.1}%", put.prob_assignment * 100.0)
+            ]);
+        }
+        
+        println!("\nCash-Secured Puts:");
+        table.printstd();
+    }
+    
+    fn display_vertical_spreads(&self, spreads: &[&VerticalSpread]) {
+        use prettytable::{Table, row};
+        
+        if spreads.is_empty() {
+            println!("No vertical spreads met the limits.");
+            return;
+        }
+        
+        let mut table = Table::new();
+        table.add_row(row![
+            "Symbol",
+            "Spread",
+            "Expiry",
+            "Long",
+            "Short",
+            "Width",
+            "Debit/Credit",
+            "Max Profit",
+            "Max Loss",
+            "Breakeven",
+            "Reward/Risk",
+            "Stock Price"
+        ]);
+        
+        for spread in spreads {
+            let premium = if spread.kind.is_credit() {
+                format!("${:.2} cr", spread.net_premium)
+            } else {
+                format!("${:.2} db", spread.net_premium)
+            };
+            
+            table.add_row(row![
+                spread.symbol,
+                spread.kind,
+                spread.expiration.format("%Y-%m-%d"),
+                format!("${:.2}", spread.long_strike),
+                format!("${:.2}", spread.short_strike),
+                format!("${:.2}", spread.width),
+                premium,
+                format!("${:.0}", spread.max_profit),
+                format!("${:.0}", spread.max_loss),
+                format!("${:.2}", spread.breakeven),
+                format!("{:.2}", spread.reward_risk),
+                format!("${:.2}", spread.stock_price)
+            ]);
+        }
+        
+        println!("\nVertical Spreads (best per symbol):");
+        table.printstd();
+    }
+    
+    fn display_neutral_setups(&self, setups: &[&NeutralSetup]) {
+        use prettytable::{Table, row};
+        
+        if setups.is_empty() {
+            println!("No neutral setups met the limits.");
+            return;
+        }
+        
+        let dollars = |value: Option<f64>| {
+            value.map_or_else(|| "unlimited".to_string(), |v| format!("${:.0}", v))
+        };
+        
+        let mut table = Table::new();
+        table.add_row(row![
+            "Symbol",
+            "Strategy",
+            "Expiry",
+            "Strikes",
+            "Premium",
+            "Max Profit",
+            "Max Loss",
+            "Breakevens",
+            "ATM IV",
+            "Exp. Move",
+            "Move Ratio"
+        ]);
+        
+        for setup in setups {
+            let strikes = match (setup.put_wing, setup.call_wing) {
+                (Some(put_wing), Some(call_wing)) => format!(
+                    "{:.0}/{:.0}/{:.0}/{:.0}",
+                    put_wing, setup.put_strike, setup.call_strike, call_wing
+                ),
+                _ if setup.put_strike == setup.call_strike => format!("{:.0}", setup.put_strike),
+                _ => format!("{:.0}/{:.0}", setup.put_strike, setup.call_strike),
+            };
+            
+            table.add_row(row![
+                setup.symbol,
+                setup.strategy,
+                setup.expiration.format("%Y-%m-%d"),
+                strikes,
+                format!("${:.2}", setup.net_premium),
+                dollars(setup.max_profit),
+                dollars(setup.max_loss),
+                format!("${:.2}-${:.2}", setup.lower_breakeven, setup.upper_breakeven),
+                format!("{:.1}%", setup.atm_iv * 100.0),
+                format!("\u{00b1}${:.2}", setup.expected_move),
+                format!("{:.2}", setup.move_ratio)
+            ]);
+        }
+        
+        println!("\nNeutral Setups (move ratio > 1 favours the trade):");
+        table.printstd();
+    }
+    
+    fn display_calendar_spreads(&self, spreads: &[&CalendarSpread]) {
+        use prettytable::{Table, row};
+        
+        if spreads.is_empty() {
+            println!("No calendar or diagonal spreads met the limits.");
+            return;
+        }
+        
+        let mut table = Table::new();
+        table.add_row(row![
+            "Symbol",
+            "Spread",
+            "Type",
+            "Front",
+            "Back",
+            "Strikes",
+            "Front IV",
+            "Back IV",
+            "IV Diff",
+            "Net Debit",
+            "Theta",
+            "Vega"
+        ]);
+        
+        for spread in spreads {
+            table.add_row(row![
+                spread.symbol,
+                spread.kind,
+                spread.option_type,
+                spread.front_expiration.format("%Y-%m-%d"),
+                spread.back_expiration.format("%Y-%m-%d"),
+                format!("{:.0}/{:.0}", spread.front_strike, spread.back_strike),
+                format!("{:.1}%", spread.front_iv * 100.0),
+                format!("{:.1}%", spread.back_iv * 100.0),
+                format!("{:+.1}", spread.iv_differential),
+                format!("${:.2}", spread.net_debit),
+                format!("{:.2}", spread.theta),
+                format!("{:.2}", spread.vega)
+            ]);
+        }
+        
+        println!("\nCalendar/Diagonal Spreads (front-month IV richest first):");
+        table.printstd();
+    }
+    
+    fn display_candidates(&self, candidates: &[&Candidate]) {
+        use prettytable::{Table, row};
+        
+        let mut table = Table::new();
+        table.add_row(row!["Strategy", "Symbol", "Score", "Legs", "Metrics"]);
+        
+        for candidate in candidates {
+            let metrics = candidate
+                .metrics
+                .iter()
+                .map(|(name, value)| format!("{}={:.2}", name, value))
+                .collect::<Vec<_>>()
+                .join(" ");
+            
+            table.add_row(row![
+                candidate.strategy,
+                candidate.symbol,
+                format!("{:.2}", candidate.score),
+                candidate.contract_ids.join("+"),
+                metrics
+            ]);
+        }
+        
+        println!("\nStrategy Candidates:");
+        table.printstd();
+    }
+    
+    fn display_changes(&self, changes: &[OpportunityChange]) {
+        use prettytable::{Table, row};
+        
+        if changes.is_empty() {
+            println!("No changes since the last cycle.");
+            return;
+        }
+        
+        let count = |kind| changes.iter().filter(|change| change.change == kind).count();
+        let shown = |value: Option<f64>, unit: &str| {
+            value.map_or_else(|| "-".to_string(), |value| format!("{:.2}{}", value, unit))
+        };
+        
+        let mut table = Table::new();
+        table.add_row(row![
+            "Change",
+            "Symbol",
+            "Type",
+            "Expiry",
+            "Strike",
+            "Price",
+            "Profit %",
+            "IV %"
+        ]);
+        
+        for change in changes {
+            table.add_row(row![
+                change.change,
+                change.symbol,
+                change.option_type,
+                change.expiration.format("%Y-%m-%d"),
+                format!("${:.2}", change.strike),
+                format!(
+                    "{} -> {}",
+                    shown(change.previous_price, ""),
+                    shown(change.current_price, "")
+                ),
+                format!(
+                    "{} -> {}",
+                    shown(change.previous_profit, "%"),
+                    shown(change.current_profit, "%")
+                ),
+                format!(
+                    "{} -> {}",
+                    shown(change.previous_iv, "%"),
+                    shown(change.current_iv, "%")
+                )
+            ]);
+        }
+        
+        println!(
+            "\nChanges Since Last Cycle ({} new, {} changed, {} gone):",
+            count(ChangeKind::New),
+            count(ChangeKind::Changed),
+            count(ChangeKind::Gone)
+        );
+        table.printstd();
+    }
+    
+    fn display_candidate_changes(&self, changes: &[CandidateChange]) {
+        use prettytable::{Table, row};
+        
+        if changes.is_empty() {
+            return;
+        }
+        
+        let shown = |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| format!("{:.2}", v));
+        
+        let mut table = Table::new();
+        table.add_row(row!["Change", "Strategy", "Symbol", "Legs", "Score"]);
+        
+        for change in changes {
+            table.add_row(row![
+                change.change,
+                change.strategy,
+                change.symbol,
+                change.contract_ids,
+                format!("{} -> {}", shown(change.previous_score), shown(change.current_score))
+            ]);
+        }
+        
+        println!("\nStrategy Candidate Changes Since Last Cycle:");
+        table.printstd();
+    }
+    
+    fn display_timings(&self, timings: &[SymbolScanTiming]) {
+        use prettytable::{Table, row};
+        
+        if timings.is_empty() {
+            return;
+        }
+        
+        let mut table = Table::new();
+        table.add_row(row!["Symbol", "Time", "Found", "Status"]);
+        
+        for timing in timings {
+            table.add_row(row![
+                timing.symbol,
+                format!("{:.2}s", timing.elapsed.as_secs_f64()),
+                timing.options_found,
+                timing.error.as_deref().unwrap_or("ok")
+            ]);
+        }
+        
+        let total: Duration = timings.iter().map(|t| t.elapsed).sum();
+        println!(
+            "\nSymbol Timings ({:.2}s of symbol work, {} in parallel):",
+            total.as_secs_f64(),
+            self.config.max_concurrent_symbols
+        );
         table.printstd();
     }
 }
 
-// Public run function that creates and runs the scanner
-pub async fn run() -> Result<(), ScannerError> {
-    let config = ScannerConfig::from_env()
-        .map_err(|e| ScannerError::ConfigError(e.to_string()))?;
+// Named watchlist files take precedence over the plain NASDAQ_STOCKS symbol list
+pub fn load_watchlist(config: &ScannerConfig) -> Result<Watchlist, WatchlistError> {
+    match &config.watchlist {
+        Some(names) => Watchlist::load_named(names, &config.watchlist_dir),
+        None => Watchlist::from_symbols("NASDAQ_STOCKS", &config.nasdaq_stocks),
+    }
+}
+
+// Returns what to diff this cycle against and what to keep for the next. A symbol that failed
+// to scan is left out of the comparison, so it isn't reported gone now and new next cycle, and
+// its last good results carry forward.
+fn next_baseline(
+    previous: Option<ScanResults>,
+    current: &ScanResults,
+    failed: &HashSet<&str>,
+) -> (Option<ScanResults>, ScanResults) {
+    let mut snapshot = ScanResults {
+        options: current.options.clone(),
+        candidates: current.candidates.clone(),
+        ..ScanResults::default()
+    };
+    
+    let baseline = previous.map(|mut baseline| {
+        let mut carried = baseline.clone();
+        carried.retain_symbols(|symbol| failed.contains(symbol));
+        snapshot.append(&mut carried);
+        
+        baseline.retain_symbols(|symbol| !failed.contains(symbol));
+        baseline
+    });
     
-    let client = IbkrClient::new(
+    (baseline, snapshot)
+}
+
+// Next announced dividend projected quarterly out to the furthest expiry in the chain
+fn dividend_schedule(
+    quote: &UnderlyingQuote,
+    chains: &[OptionChain],
+    now: DateTime<Utc>,
+) -> Vec<Dividend> {
+    let (Some(amount), Some(ex_date)) = (quote.dividend_amount, quote.ex_dividend_date) else {
+        return Vec::new();
+    };
+    
+    let horizon = chains.iter().map(|chain| chain.expiration).max().unwrap_or(now);
+    project_dividends(amount, ex_date, now, horizon)
+}
+
+pub fn build_client(config: &ScannerConfig) -> IbkrClient {
+    IbkrClient::new(
         config.base_url(),
         config.ibkr_account_id.clone(),
         config.request_timeout_seconds,
-    );
-    
-    let scanner = NasdaqScanner::new(client, config)?;
+    )
+    .with_conid_cache(ConidCache::load(&config.conid_cache_path))
+    .with_retry_policy(RetryPolicy::from_attempts(
+        config.max_retry_attempts,
+        Duration::from_millis(config.retry_base_delay_ms),
+        Duration::from_millis(config.retry_max_delay_ms),
+    ))
+    .with_rate_limiter(RateLimiter::new(
+        config.rate_limit_per_second,
+        &config.endpoint_rate_limits,
+    ))
+}
+
+// Public run function that creates and runs the scanner until interrupted. `strategies` is
+// what config.strategies picks from: StrategyRegistry::builtin plus any of the caller's own.
+pub async fn run(config: ScannerConfig, strategies: StrategyRegistry) -> Result<(), ScannerError> {
+    let client = build_client(&config);
+    let scanner = Arc::new(NasdaqScanner::with_strategies(client, config, strategies)?);
     scanner.run_scanner().await
 }
+
+// Runs exactly one scan cycle
+pub async fn run_once(
+    config: ScannerConfig,
+    strategies: StrategyRegistry,
+) -> Result<ScanResults, ScannerError> {
+    let client = build_client(&config);
+    let scanner = Arc::new(NasdaqScanner::with_strategies(client, config, strategies)?);
+    scanner.scan_once().await
+}
+
+// Reachability plus brokerage session state, without scanning
+pub async fn check_connection(config: &ScannerConfig) -> Result<SessionState, ScannerError> {
+    let client = build_client(config);
+    
+    if !client.test_connection().await? {
+        return Err(ScannerError::ConfigError(
+            "Failed to connect to IBKR Gateway".to_string(),
+        ));
+    }
+    
+    Ok(client.auth_status().await?.session_state())
+}
+
+// The contracts that pass every filter, logging the first rejection of each one that doesn't
+fn filter_contracts(chains: &[OptionChain], filters: &OptionChainFilters) -> Vec<OptionChain> {
+    chains
+        .iter()
+        .filter(|chain| match filters.rejection(chain) {
+            Some(reason) => {
+                debug!("Filtered out {} on {}", chain.contract_id, reason);
+                false
+            }
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn option(symbol: &str, contract_id: &str) -> OptionChain {
+        OptionChain {
+            symbol: symbol.to_string(),
+            contract_id: contract_id.to_string(),
+            ..OptionChain::sample(OptionType::Call, 100.0, 2.0)
+        }
+    }
+    
+    fn candidate(symbol: &str, leg: &str) -> Candidate {
+        Candidate {
+            strategy: COVERED_CALLS.to_string(),
+            symbol: symbol.to_string(),
+            contract_ids: vec![leg.to_string()],
+            ..Candidate::default()
+        }
+    }
+    
+    fn ids(results: &ScanResults) -> (Vec<&str>, Vec<&str>) {
+        (
+            results.options.iter().map(|o| o.contract_id.as_str()).collect(),
+            results.candidates.iter().map(|c| c.contract_ids[0].as_str()).collect(),
+        )
+    }
+    
+    #[test]
+    fn test_contract_filters_apply_with_symbol_overrides() {
+        let chains = [
+            OptionChain { volume: 5, ..option("AAPL", "thin") },
+            OptionChain { volume: 500, ..option("AAPL", "busy") },
+        ];
+        let filters = OptionChainFilters {
+            min_volume: Some(100),
+            ..OptionChainFilters::default()
+        };
+        
+        let passed = filter_contracts(&chains, &filters);
+        assert_eq!(passed.len(), 1);
+        assert_eq!(passed[0].contract_id, "busy");
+        
+        let relaxed = filters.with_overrides(&OptionChainFilters {
+            min_volume: Some(1),
+            ..OptionChainFilters::default()
+        });
+        assert_eq!(filter_contracts(&chains, &relaxed).len(), 2);
+    }
+    
+    #[test]
+    fn test_failed_symbols_are_skipped_and_carried_forward() {
+        let previous = ScanResults {
+            options: vec![option("AAPL", "a1"), option("MSFT", "m1")],
+            candidates: vec![candidate("AAPL", "a2"), candidate("MSFT", "m2")],
+            ..ScanResults::default()
+        };
+        // MSFT errored this cycle, so only AAPL came back
+        let current = ScanResults {
+            options: vec![option("AAPL", "a3")],
+            ..ScanResults::default()
+        };
+        let failed = HashSet::from(["MSFT"]);
+        
+        let (baseline, snapshot) = next_baseline(Some(previous), &current, &failed);
+        
+        assert_eq!(ids(&baseline.unwrap()), (vec!["a1"], vec!["a2"]));
+        assert_eq!(ids(&snapshot), (vec!["a3", "m1"], vec!["m2"]));
+        
+        let (baseline, snapshot) = next_baseline(None, &current, &HashSet::new());
+        assert!(baseline.is_none());
+        assert_eq!(ids(&snapshot), (vec!["a3"], vec![]));
+    }
+}
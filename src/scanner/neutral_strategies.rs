@@ -0,0 +1,417 @@
+use crate::models::{NeutralStrategyConfig, OptionChain, OptionType};
+use crate::scanner::strategy::{
+    Candidate, CandidateDetail, Strategy, StrategyInput, NEUTRAL_STRATEGIES,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum NeutralStrategy {
+    IronCondor,
+    ShortStrangle,
+    LongStraddle,
+}
+
+impl fmt::Display for NeutralStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NeutralStrategy::IronCondor => write!(f, "Iron Condor"),
+            NeutralStrategy::ShortStrangle => write!(f, "Short Strangle"),
+            NeutralStrategy::LongStraddle => write!(f, "Long Straddle"),
+        }
+    }
+}
+
+// A two- or four-leg setup for one expiry. `net_premium` is per share, credit for the short
+// strategies and debit for the straddle, net of trading costs; profit and loss are per set of
+// contracts, None when unlimited. `move_ratio` compares the setup with the expected move
+// implied by ATM IV: for short setups it is breakeven distance over expected move, for the
+// straddle expected move over debit, so above 1 favours the trade either way.
+#[derive(Debug, Clone, Serialize)]
+pub struct NeutralSetup {
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub strategy: NeutralStrategy,
+    pub expiration: DateTime<Utc>,
+    pub stock_price: f64,
+    pub atm_iv: f64,
+    pub expected_move: f64,
+    pub put_strike: f64,
+    pub call_strike: f64,
+    pub put_wing: Option<f64>,
+    pub call_wing: Option<f64>,
+    pub net_premium: f64,
+    pub max_profit: Option<f64>,
+    pub max_loss: Option<f64>,
+    pub lower_breakeven: f64,
+    pub upper_breakeven: f64,
+    pub move_ratio: f64,
+    pub put_contract_id: String,
+    pub call_contract_id: String,
+    pub put_wing_contract_id: Option<String>,
+    pub call_wing_contract_id: Option<String>,
+}
+
+impl NeutralSetup {
+    // Short (or straddle) legs first, then any wings
+    pub fn contract_ids(&self) -> Vec<String> {
+        [Some(&self.put_contract_id), Some(&self.call_contract_id)]
+            .into_iter()
+            .chain([self.put_wing_contract_id.as_ref(), self.call_wing_contract_id.as_ref()])
+            .flatten()
+            .cloned()
+            .collect()
+    }
+}
+
+// Evaluates every expiry in the chain, best move ratio first
+pub fn evaluate_neutral_strategies(
+    chains: &[OptionChain],
+    config: &NeutralStrategyConfig,
+) -> Vec<NeutralSetup> {
+    let mut expirations: Vec<DateTime<Utc>> =
+        chains.iter().map(|chain| chain.expiration).collect();
+    expirations.sort();
+    expirations.dedup();
+
+    let mut setups: Vec<NeutralSetup> = expirations
+        .into_iter()
+        .flat_map(|expiration| {
+            let expiry: Vec<&OptionChain> = chains
+                .iter()
+                .filter(|chain| chain.expiration == expiration && chain.option_price > 0.0)
+                .collect();
+            evaluate_expiry(&expiry, config)
+        })
+        .filter(|setup| setup.move_ratio >= config.min_move_ratio)
+        .collect();
+
+    setups.sort_by(|a, b| b.move_ratio.total_cmp(&a.move_ratio));
+    setups
+}
+
+fn evaluate_expiry(legs: &[&OptionChain], config: &NeutralStrategyConfig) -> Vec<NeutralSetup> {
+    let Some(atm) = AtmPair::find(legs) else {
+        return Vec::new();
+    };
+
+    let spot = atm.call.stock_price;
+    let years = atm.call.metrics.days_to_expiry / 365.0;
+    let atm_iv = atm.implied_volatility();
+    let expected_move = spot * atm_iv * years.sqrt();
+
+    if expected_move <= 0.0 {
+        return Vec::new();
+    }
+
+    let context = Context {
+        template: atm.call,
+        spot,
+        atm_iv,
+        expected_move,
+    };
+
+    let mut setups = vec![long_straddle(&context, &atm)];
+
+    // Short strikes about one expected move out of the money
+    let short_call = closest(legs, OptionType::Call, spot + expected_move, |c| c.strike > spot);
+    let short_put = closest(legs, OptionType::Put, spot - expected_move, |p| p.strike < spot);
+
+    if let (Some(call), Some(put)) = (short_call, short_put) {
+        setups.push(short_strangle(&context, put, call));
+
+        let long_call = closest(legs, OptionType::Call, call.strike + config.wing_width, |c| {
+            c.strike > call.strike
+        });
+        let long_put = closest(legs, OptionType::Put, put.strike - config.wing_width, |p| {
+            p.strike < put.strike
+        });
+
+        if let (Some(long_call), Some(long_put)) = (long_call, long_put) {
+            setups.extend(iron_condor(&context, put, call, long_put, long_call));
+        }
+    }
+
+    setups
+}
+
+struct Context<'a> {
+    template: &'a OptionChain,
+    spot: f64,
+    atm_iv: f64,
+    expected_move: f64,
+}
+
+impl Context<'_> {
+    // Shared fields; callers fill in legs, premium and payoff with struct update syntax
+    fn setup(&self, strategy: NeutralStrategy) -> NeutralSetup {
+        NeutralSetup {
+            timestamp: self.template.timestamp,
+            symbol: self.template.symbol.clone(),
+            strategy,
+            expiration: self.template.expiration,
+            stock_price: self.spot,
+            atm_iv: self.atm_iv,
+            expected_move: self.expected_move,
+            put_strike: 0.0,
+            call_strike: 0.0,
+            put_wing: None,
+            call_wing: None,
+            net_premium: 0.0,
+            max_profit: None,
+            max_loss: None,
+            lower_breakeven: 0.0,
+            upper_breakeven: 0.0,
+            move_ratio: 0.0,
+            put_contract_id: String::new(),
+            call_contract_id: String::new(),
+            put_wing_contract_id: None,
+            call_wing_contract_id: None,
+        }
+    }
+
+    fn per_contract(&self, per_share: f64) -> f64 {
+        per_share * self.template.multiplier.max(1.0)
+    }
+
+    // Half the breakeven range over the expected move
+    fn breakeven_ratio(&self, lower: f64, upper: f64) -> f64 {
+        (upper - lower) / 2.0 / self.expected_move
+    }
+}
+
+// Call and put at the strike nearest the stock price
+struct AtmPair<'a> {
+    call: &'a OptionChain,
+    put: &'a OptionChain,
+}
+
+impl<'a> AtmPair<'a> {
+    fn find(legs: &[&'a OptionChain]) -> Option<Self> {
+        legs.iter()
+            .filter(|call| call.option_type == OptionType::Call)
+            .filter_map(|call| {
+                let put = legs
+                    .iter()
+                    .find(|put| put.option_type == OptionType::Put && put.strike == call.strike)?;
+                Some(AtmPair { call, put })
+            })
+            .min_by(|a, b| {
+                let distance = |pair: &AtmPair| (pair.call.strike - pair.call.stock_price).abs();
+                distance(a).total_cmp(&distance(b))
+            })
+    }
+
+    // Average of the usable leg IVs
+    fn implied_volatility(&self) -> f64 {
+        let ivs: Vec<f64> = [self.call, self.put]
+            .iter()
+            .filter(|leg| leg.iv_status.is_usable() && leg.implied_volatility > 0.0)
+            .map(|leg| leg.implied_volatility)
+            .collect();
+
+        if ivs.is_empty() {
+            0.0
+        } else {
+            ivs.iter().sum::<f64>() / ivs.len() as f64
+        }
+    }
+}
+
+fn closest<'a>(
+    legs: &[&'a OptionChain],
+    option_type: OptionType,
+    target: f64,
+    allowed: impl Fn(&OptionChain) -> bool,
+) -> Option<&'a OptionChain> {
+    legs.iter()
+        .copied()
+        .filter(|leg| leg.option_type == option_type && allowed(leg))
+        .min_by(|a, b| (a.strike - target).abs().total_cmp(&(b.strike - target).abs()))
+}
+
+fn long_straddle(context: &Context, atm: &AtmPair) -> NeutralSetup {
+    let debit = atm.call.cost_basis() + atm.put.cost_basis();
+    let strike = atm.call.strike;
+
+    NeutralSetup {
+        put_strike: strike,
+        call_strike: strike,
+        net_premium: debit,
+        max_loss: Some(context.per_contract(debit)),
+        lower_breakeven: strike - debit,
+        upper_breakeven: strike + debit,
+        move_ratio: if debit > 0.0 { context.expected_move / debit } else { 0.0 },
+        put_contract_id: atm.put.contract_id.clone(),
+        call_contract_id: atm.call.contract_id.clone(),
+        ..context.setup(NeutralStrategy::LongStraddle)
+    }
+}
+
+fn short_strangle(context: &Context, put: &OptionChain, call: &OptionChain) -> NeutralSetup {
+    let credit = put.short_proceeds() + call.short_proceeds();
+    let (lower, upper) = (put.strike - credit, call.strike + credit);
+
+    NeutralSetup {
+        put_strike: put.strike,
+        call_strike: call.strike,
+        net_premium: credit,
+        max_profit: Some(context.per_contract(credit)),
+        lower_breakeven: lower,
+        upper_breakeven: upper,
+        move_ratio: context.breakeven_ratio(lower, upper),
+        put_contract_id: put.contract_id.clone(),
+        call_contract_id: call.contract_id.clone(),
+        ..context.setup(NeutralStrategy::ShortStrangle)
+    }
+}
+
+fn iron_condor(
+    context: &Context,
+    put: &OptionChain,
+    call: &OptionChain,
+    long_put: &OptionChain,
+    long_call: &OptionChain,
+) -> Option<NeutralSetup> {
+    let credit = put.short_proceeds() + call.short_proceeds() - long_put.cost_basis() - long_call.cost_basis();
+    let widest_wing = (put.strike - long_put.strike).max(long_call.strike - call.strike);
+    let max_loss = widest_wing - credit;
+
+    // Wings priced at or above the short legs leave nothing to collect
+    if credit <= 0.0 || max_loss <= 0.0 {
+        return None;
+    }
+
+    let (lower, upper) = (put.strike - credit, call.strike + credit);
+
+    Some(NeutralSetup {
+        put_strike: put.strike,
+        call_strike: call.strike,
+        put_wing: Some(long_put.strike),
+        call_wing: Some(long_call.strike),
+        net_premium: credit,
+        max_profit: Some(context.per_contract(credit)),
+        max_loss: Some(context.per_contract(max_loss)),
+        lower_breakeven: lower,
+        upper_breakeven: upper,
+        move_ratio: context.breakeven_ratio(lower, upper),
+        put_contract_id: put.contract_id.clone(),
+        call_contract_id: call.contract_id.clone(),
+        put_wing_contract_id: Some(long_put.contract_id.clone()),
+        call_wing_contract_id: Some(long_call.contract_id.clone()),
+        ..context.setup(NeutralStrategy::IronCondor)
+    })
+}
+
+pub struct NeutralStrategies(pub NeutralStrategyConfig);
+
+impl Strategy for NeutralStrategies {
+    fn name(&self) -> &str {
+        NEUTRAL_STRATEGIES
+    }
+
+    // Condor wings sit well outside the expected move, where volume is thin
+    fn uses_contract_filters(&self) -> bool {
+        false
+    }
+
+    fn evaluate(&self, input: &StrategyInput) -> Vec<Candidate> {
+        evaluate_neutral_strategies(input.chains, &self.0)
+            .into_iter()
+            .map(|setup| Candidate {
+                timestamp: setup.timestamp,
+                strategy: NEUTRAL_STRATEGIES.to_string(),
+                symbol: setup.symbol.clone(),
+                score: setup.move_ratio,
+                contract_ids: setup.contract_ids(),
+                metrics: vec![
+                    ("net_premium".to_string(), setup.net_premium),
+                    ("expected_move".to_string(), setup.expected_move),
+                    ("move_ratio".to_string(), setup.move_ratio),
+                ],
+                detail: CandidateDetail::Neutral(setup),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::OptionMetrics;
+
+    fn leg(option_type: OptionType, strike: f64, option_price: f64) -> OptionChain {
+        OptionChain {
+            implied_volatility: 0.5,
+            metrics: OptionMetrics {
+                days_to_expiry: 36.5,
+                ..OptionMetrics::default()
+            },
+            ..OptionChain::sample(option_type, strike, option_price)
+        }
+    }
+
+    fn chain() -> Vec<OptionChain> {
+        // Expected move: 100 * 0.5 * sqrt(0.1) = 15.81
+        vec![
+            leg(OptionType::Put, 80.0, 0.8),
+            leg(OptionType::Put, 85.0, 1.5),
+            leg(OptionType::Put, 100.0, 6.0),
+            leg(OptionType::Call, 100.0, 6.5),
+            leg(OptionType::Call, 115.0, 1.7),
+            leg(OptionType::Call, 120.0, 0.9),
+        ]
+    }
+
+    fn find(setups: &[NeutralSetup], strategy: NeutralStrategy) -> &NeutralSetup {
+        setups.iter().find(|s| s.strategy == strategy).unwrap()
+    }
+
+    #[test]
+    fn test_long_straddle_against_expected_move() {
+        let setups = evaluate_neutral_strategies(&chain(), &NeutralStrategyConfig::default());
+        let straddle = find(&setups, NeutralStrategy::LongStraddle);
+
+        assert!((straddle.expected_move - 15.811).abs() < 1e-3);
+        assert_eq!(straddle.net_premium, 12.5);
+        assert_eq!(straddle.max_profit, None);
+        assert_eq!(straddle.max_loss, Some(1250.0));
+        assert_eq!((straddle.lower_breakeven, straddle.upper_breakeven), (87.5, 112.5));
+        assert!((straddle.move_ratio - 15.811 / 12.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_short_strangle_and_iron_condor() {
+        let setups = evaluate_neutral_strategies(&chain(), &NeutralStrategyConfig::default());
+
+        let strangle = find(&setups, NeutralStrategy::ShortStrangle);
+        assert_eq!((strangle.put_strike, strangle.call_strike), (85.0, 115.0));
+        assert!((strangle.net_premium - 3.2).abs() < 1e-9);
+        assert_eq!(strangle.max_loss, None);
+
+        let condor = find(&setups, NeutralStrategy::IronCondor);
+        assert_eq!((condor.put_wing, condor.call_wing), (Some(80.0), Some(120.0)));
+        assert!((condor.net_premium - 1.5).abs() < 1e-9);
+        assert!((condor.max_loss.unwrap() - 350.0).abs() < 1e-9);
+        assert!((condor.lower_breakeven - 83.5).abs() < 1e-9);
+        assert!(condor.move_ratio < strangle.move_ratio);
+        assert_eq!(
+            condor.contract_ids(),
+            vec!["20250615P85", "20250615C115", "20250615P80", "20250615C120"]
+        );
+    }
+
+    #[test]
+    fn test_min_move_ratio_filters() {
+        let strict = NeutralStrategyConfig {
+            min_move_ratio: 1.1,
+            ..NeutralStrategyConfig::default()
+        };
+
+        let setups = evaluate_neutral_strategies(&chain(), &strict);
+
+        assert!(setups.iter().all(|s| s.move_ratio >= 1.1));
+        assert!(setups.iter().any(|s| s.strategy == NeutralStrategy::LongStraddle));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::models::OptionType;
+use chrono::{DateTime, Utc};
+use std::f64::consts::{PI, SQRT_2};
+
+const DAYS_PER_YEAR: f64 = 365.0;
+const SECONDS_PER_YEAR: f64 = DAYS_PER_YEAR * 24.0 * 3600.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PricingInputs {
+    pub spot: f64,
+    pub strike: f64,
+    pub time_to_expiry: f64, // years
+    pub rate: f64,           // continuously compounded, 0.05 = 5%
+    pub dividend_yield: f64, // continuous, 0.01 = 1%
+    pub volatility: f64,     // annualized, 0.25 = 25%
+}
+
+// Theoretical value and sensitivities in trader units:
+// theta per calendar day, vega and rho per 1 percentage point
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Greeks {
+    pub value: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub theta: f64,
+    pub vega: f64,
+    pub rho: f64,
+}
+
+pub fn years_to_expiry(expiration: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    ((expiration - now).num_seconds() as f64 / SECONDS_PER_YEAR).max(0.0)
+}
+
+pub fn black_scholes(option_type: &OptionType, inputs: &PricingInputs) -> Greeks {
+    let PricingInputs {
+        spot,
+        strike,
+        time_to_expiry: t,
+        rate: r,
+        dividend_yield: q,
+        volatility: sigma,
+    } = *inputs;
+
+    if spot <= 0.0 || strike <= 0.0 {
+        return Greeks::default();
+    }
+
+    // At expiry, or with no volatility, the option is worth its discounted intrinsic value
+    if t <= 0.0 || sigma <= 0.0 {
+        return intrinsic_greeks(option_type, inputs);
+    }
+
+    let sqrt_t = t.sqrt();
+    let d1 = ((spot / strike).ln() + (r - q + 0.5 * sigma * sigma) * t) / (sigma * sqrt_t);
+    let d2 = d1 - sigma * sqrt_t;
+
+    let spot_discount = (-q * t).exp();
+    let strike_discount = (-r * t).exp();
+    let pdf_d1 = norm_pdf(d1);
+
+    let gamma = spot_discount * pdf_d1 / (spot * sigma * sqrt_t);
+    let vega = spot * spot_discount * pdf_d1 * sqrt_t;
+    let decay = -spot * spot_discount * pdf_d1 * sigma / (2.0 * sqrt_t);
+
+    let (value, delta, theta, rho) = match option_type {
+        OptionType::Call => (
+            spot * spot_discount * norm_cdf(d1) - strike * strike_discount * norm_cdf(d2),
+            spot_discount * norm_cdf(d1),
+            decay - r * strike * strike_discount * norm_cdf(d2)
+                + q * spot * spot_discount * norm_cdf(d1),
+            strike * t * strike_discount * norm_cdf(d2),
+        ),
+        OptionType::Put => (
+            strike * strike_discount * norm_cdf(-d2) - spot * spot_discount * norm_cdf(-d1),
+            -spot_discount * norm_cdf(-d1),
+            decay + r * strike * strike_discount * norm_cdf(-d2)
+                - q * spot * spot_discount * norm_cdf(-d1),
+            -strike * t * strike_discount * norm_cdf(-d2),
+        ),
+    };
+
+    Greeks {
+        value,
+        delta,
+        gamma,
+        theta: theta / DAYS_PER_YEAR,
+        vega: vega / 100.0,
+        rho: rho / 100.0,
+    }
+}
+
+fn intrinsic_greeks(option_type: &OptionType, inputs: &PricingInputs) -> Greeks {
+    let t = inputs.time_to_expiry.max(0.0);
+    let forward = inputs.spot * ((inputs.rate - inputs.dividend_yield) * t).exp();
+    let discount = (-inputs.rate * t).exp();
+
+    let (value, delta) = match option_type {
+        OptionType::Call if forward > inputs.strike => {
+            (discount * (forward - inputs.strike), (-inputs.dividend_yield * t).exp())
+        }
+        OptionType::Put if forward < inputs.strike => {
+            (discount * (inputs.strike - forward), -(-inputs.dividend_yield * t).exp())
+        }
+        _ => (0.0, 0.0),
+    };
+
+    Greeks {
+        value,
+        delta,
+        ..Greeks::default()
+    }
+}
+
+pub fn norm_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * PI).sqrt()
+}
+
+pub fn norm_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / SQRT_2)
+}
+
+// Complementary error function, Chebyshev fit from Numerical Recipes (|error| < 1.2e-7)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87
+                                    + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let result = t * poly.exp();
+
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> PricingInputs {
+        PricingInputs {
+            spot: 100.0,
+            strike: 100.0,
+            time_to_expiry: 1.0,
+            rate: 0.05,
+            dividend_yield: 0.0,
+            volatility: 0.2,
+        }
+    }
+
+    #[test]
+    fn test_norm_cdf() {
+        assert!((norm_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((norm_cdf(1.96) - 0.975_002).abs() < 1e-6);
+        assert!((norm_cdf(-1.0) - 0.158_655).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_black_scholes_reference_values() {
+        let call = black_scholes(&OptionType::Call, &inputs());
+        let put = black_scholes(&OptionType::Put, &inputs());
+
+        // Hull, Options Futures and Other Derivatives reference case
+        assert!((call.value - 10.4506).abs() < 1e-3);
+        assert!((put.value - 5.5735).abs() < 1e-3);
+        assert!((call.delta - 0.6368).abs() < 1e-3);
+        assert!((put.delta + 0.3632).abs() < 1e-3);
+        assert!((call.gamma - 0.01876).abs() < 1e-4);
+        assert!((call.vega - 0.3752).abs() < 1e-3);
+        assert!((call.theta + 6.414 / 365.0).abs() < 1e-4);
+        assert!((call.rho - 0.5323).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_put_call_parity_with_dividends() {
+        let inputs = PricingInputs {
+            dividend_yield: 0.02,
+            strike: 95.0,
+            time_to_expiry: 0.5,
+            ..inputs()
+        };
+
+        let call = black_scholes(&OptionType::Call, &inputs);
+        let put = black_scholes(&OptionType::Put, &inputs);
+        let parity = inputs.spot * (-inputs.dividend_yield * inputs.time_to_expiry).exp()
+            - inputs.strike * (-inputs.rate * inputs.time_to_expiry).exp();
+
+        assert!((call.value - put.value - parity).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_expired_option_is_intrinsic() {
+        let expired = PricingInputs {
+            spot: 110.0,
+            time_to_expiry: 0.0,
+            ..inputs()
+        };
+
+        assert!((black_scholes(&OptionType::Call, &expired).value - 10.0).abs() < 1e-9);
+        assert_eq!(black_scholes(&OptionType::Put, &expired).value, 0.0);
+    }
+}
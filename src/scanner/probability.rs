@@ -0,0 +1,155 @@
+use crate::models::OptionType;
+use crate::scanner::pricing::{black_scholes, norm_cdf, PricingInputs};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProbabilityMetrics {
+    pub breakeven: f64,
+    pub prob_itm: f64,
+    pub prob_profit: f64,
+    pub prob_touch: f64,
+    pub expected_value: f64, // per share, at expiry, net of premium
+}
+
+// Lognormal (risk-neutral drift r - q) odds for a long option bought at `premium`
+pub fn probability_metrics(
+    option_type: &OptionType,
+    premium: f64,
+    inputs: &PricingInputs,
+) -> ProbabilityMetrics {
+    let breakeven = match option_type {
+        OptionType::Call => inputs.strike + premium,
+        OptionType::Put => (inputs.strike - premium).max(0.0),
+    };
+
+    let t = inputs.time_to_expiry;
+    let sigma = inputs.volatility;
+
+    if inputs.spot <= 0.0 || t <= 0.0 || sigma <= 0.0 {
+        return ProbabilityMetrics {
+            breakeven,
+            ..ProbabilityMetrics::default()
+        };
+    }
+
+    let prob_itm = prob_beyond(option_type, inputs, inputs.strike);
+    let prob_profit = prob_beyond(option_type, inputs, breakeven);
+    let prob_touch = prob_touch(option_type, inputs, breakeven);
+
+    // Undiscounted expected payoff is the forward value of the option
+    let expected_payoff = black_scholes(option_type, inputs).value * (inputs.rate * t).exp();
+
+    ProbabilityMetrics {
+        breakeven,
+        prob_itm,
+        prob_profit,
+        prob_touch,
+        expected_value: expected_payoff - premium,
+    }
+}
+
+// P(S_T > level) for calls, P(S_T < level) for puts
+fn prob_beyond(option_type: &OptionType, inputs: &PricingInputs, level: f64) -> f64 {
+    if level <= 0.0 {
+        return match option_type {
+            OptionType::Call => 1.0,
+            OptionType::Put => 0.0,
+        };
+    }
+
+    let t = inputs.time_to_expiry;
+    let sigma = inputs.volatility;
+    let d2 = ((inputs.spot / level).ln()
+        + (inputs.rate - inputs.dividend_yield - 0.5 * sigma * sigma) * t)
+        / (sigma * t.sqrt());
+
+    match option_type {
+        OptionType::Call => norm_cdf(d2),
+        OptionType::Put => norm_cdf(-d2),
+    }
+}
+
+// Probability the stock trades through `level` at any time before expiry (reflection
+// principle for Brownian motion with drift)
+fn prob_touch(option_type: &OptionType, inputs: &PricingInputs, level: f64) -> f64 {
+    let already_through = match option_type {
+        OptionType::Call => inputs.spot >= level,
+        OptionType::Put => inputs.spot <= level,
+    };
+    if already_through {
+        return 1.0;
+    }
+    if level <= 0.0 {
+        return 0.0;
+    }
+
+    let t = inputs.time_to_expiry;
+    let sigma = inputs.volatility;
+    let mu = inputs.rate - inputs.dividend_yield - 0.5 * sigma * sigma;
+    let b = (level / inputs.spot).ln();
+    let vol_t = sigma * t.sqrt();
+
+    // Reflect a downward barrier into the upward case
+    let (b, mu) = match option_type {
+        OptionType::Call => (b, mu),
+        OptionType::Put => (-b, -mu),
+    };
+
+    let probability = norm_cdf((-b + mu * t) / vol_t)
+        + (2.0 * mu * b / (sigma * sigma)).exp() * norm_cdf((-b - mu * t) / vol_t);
+
+    probability.clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs() -> PricingInputs {
+        PricingInputs {
+            spot: 100.0,
+            strike: 105.0,
+            time_to_expiry: 30.0 / 365.0,
+            rate: 0.0,
+            dividend_yield: 0.0,
+            volatility: 0.3,
+        }
+    }
+
+    #[test]
+    fn test_probabilities_are_ordered() {
+        let metrics = probability_metrics(&OptionType::Call, 1.5, &inputs());
+
+        assert_eq!(metrics.breakeven, 106.5);
+        assert!(metrics.prob_itm > metrics.prob_profit);
+        // Touching is roughly twice as likely as finishing beyond the level
+        assert!(metrics.prob_touch > metrics.prob_profit);
+        assert!(metrics.prob_touch < 2.0 * metrics.prob_profit + 0.02);
+    }
+
+    #[test]
+    fn test_expected_value_is_zero_at_fair_price() {
+        let fair = black_scholes(&OptionType::Put, &inputs()).value;
+        let metrics = probability_metrics(&OptionType::Put, fair, &inputs());
+
+        assert!(metrics.expected_value.abs() < 1e-9);
+        assert!(probability_metrics(&OptionType::Put, fair * 2.0, &inputs()).expected_value < 0.0);
+    }
+
+    #[test]
+    fn test_put_itm_probability_matches_call_complement() {
+        let call = probability_metrics(&OptionType::Call, 1.0, &inputs());
+        let put = probability_metrics(&OptionType::Put, 1.0, &inputs());
+
+        assert!((call.prob_itm + put.prob_itm - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_touch_is_certain_when_already_through_breakeven() {
+        let itm = PricingInputs {
+            spot: 120.0,
+            ..inputs()
+        };
+
+        assert_eq!(probability_metrics(&OptionType::Call, 2.0, &itm).prob_touch, 1.0);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::models::OptionType;
+use crate::models::{OptionMetrics, OptionType};
+
+// Same-day expiries are annualized as one day so the figure stays finite
+const MIN_ANNUALIZATION_DAYS: f64 = 1.0;
 
 pub fn calculate_profit_percent(
     strike: f64,
@@ -12,7 +15,57 @@ pub fn calculate_profit_percent(
     }
 }
 
-fn calculate_call_profit(strike: f64, option_price: f64, stock_price: f64) -> f64 {
+// Breakeven, max gain/loss per contract and time-adjusted returns for a long option. The
+// return assumes the stock makes a one standard deviation move (at `volatility`) in the
+// option's favour by expiry; without a volatility there is no target and it stays at zero.
+pub fn calculate_metrics(
+    strike: f64,
+    option_price: f64,
+    stock_price: f64,
+    multiplier: f64,
+    days_to_expiry: f64,
+    volatility: f64,
+    option_type: &OptionType,
+) -> OptionMetrics {
+    let expected_move = (volatility.max(0.0) * (days_to_expiry.max(0.0) / 365.0).sqrt()).exp();
+    
+    let (breakeven, target_value, max_gain) = match option_type {
+        OptionType::Call => (
+            strike + option_price,
+            (stock_price * expected_move - strike).max(0.0),
+            None,
+        ),
+        OptionType::Put => (
+            (strike - option_price).max(0.0),
+            (strike - stock_price / expected_move).max(0.0),
+            Some((strike - option_price).max(0.0) * multiplier),
+        ),
+    };
+    
+    let return_on_capital = if option_price > 0.0 && volatility > 0.0 {
+        (target_value - option_price) / option_price * 100.0
+    } else {
+        0.0
+    };
+    
+    // Gains scale with the periods in a year; a loss can't exceed the premium at risk
+    let annualized_return = if return_on_capital > 0.0 {
+        return_on_capital * 365.0 / days_to_expiry.max(MIN_ANNUALIZATION_DAYS)
+    } else {
+        return_on_capital
+    };
+    
+    OptionMetrics {
+        breakeven,
+        max_gain,
+        max_loss: option_price * multiplier,
+        return_on_capital,
+        annualized_return,
+        days_to_expiry,
+    }
+}
+
+pub fn calculate_call_profit(strike: f64, option_price: f64, stock_price: f64) -> f64 {
     if strike <= 0.0 || option_price <= 0.0 {
         return 0.0;
     }
@@ -26,7 +79,7 @@ fn calculate_call_profit(strike: f64, option_price: f64, stock_price: f64) -> f6
     }
 }
 
-fn calculate_put_profit(strike: f64, option_price: f64, stock_price: f64) -> f64 {
+pub fn calculate_put_profit(strike: f64, option_price: f64, stock_price: f64) -> f64 {
     if strike <= 0.0 || option_price <= 0.0 {
         return 0.0;
     }
@@ -67,6 +120,42 @@ mod tests {
         assert_eq!(profit, 0.0);
     }
     
+    #[test]
+    fn test_metrics_per_contract() {
+        // 40% vol over 36.5 days is a 0.4 * sqrt(0.1) log move, to about 113.4
+        let call = calculate_metrics(100.0, 5.0, 100.0, 100.0, 36.5, 0.4, &OptionType::Call);
+        let target = 100.0 * (0.4 * 0.1_f64.sqrt()).exp();
+        assert_eq!(call.breakeven, 105.0);
+        assert_eq!(call.max_gain, None);
+        assert_eq!(call.max_loss, 500.0);
+        assert!((call.return_on_capital - (target - 105.0) / 5.0 * 100.0).abs() < 1e-9);
+        assert!((call.annualized_return - call.return_on_capital * 10.0).abs() < 1e-9);
+        
+        let put = calculate_metrics(100.0, 2.0, 105.0, 100.0, 0.2, 0.3, &OptionType::Put);
+        assert_eq!(put.breakeven, 98.0);
+        assert_eq!(put.max_gain, Some(9800.0));
+        // Out of reach of a same-day move: the premium is lost, and the loss isn't annualized
+        assert!((put.return_on_capital + 100.0).abs() < 1e-9);
+        assert!((put.annualized_return + 100.0).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_otm_return_is_forward_looking() {
+        // 5% OTM call for 1.00 with 30 days left: a one sigma move at 50% vol reaches ~115.5
+        let call = calculate_metrics(105.0, 1.0, 100.0, 100.0, 30.0, 0.5, &OptionType::Call);
+        assert!(call.return_on_capital > 900.0);
+        
+        // Same-day expiry annualizes over one day, not a fraction of one
+        let expiring = calculate_metrics(100.0, 1.0, 100.0, 100.0, 0.2, 0.5, &OptionType::Put);
+        assert!(expiring.return_on_capital > 0.0);
+        assert!((expiring.annualized_return - expiring.return_on_capital * 365.0).abs() < 1e-6);
+        
+        // No volatility, no target
+        let unpriced = calculate_metrics(105.0, 1.0, 100.0, 100.0, 30.0, 0.0, &OptionType::Call);
+        assert_eq!(unpriced.return_on_capital, 0.0);
+        assert_eq!(unpriced.annualized_return, 0.0);
+    }
+    
     #[test]
     fn test_profit_calculator() {
         let call_profit = calculate_profit_percent(
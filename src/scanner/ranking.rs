@@ -0,0 +1,293 @@
+use crate::models::{OptionChain, RankingConfig};
+use crate::scanner::strategy::Candidate;
+use std::collections::HashMap;
+
+// Factor order: return, probability, liquidity, IV rank
+const FACTORS: usize = 4;
+
+// Scores every option (0-100 when all weights are positive) and orders them best first. The
+// return factor is the one sigma move return, which already scales with time to expiry.
+pub fn rank_options(options: &mut [OptionChain], config: &RankingConfig) {
+    if options.is_empty() {
+        return;
+    }
+
+    let weights = [
+        config.return_weight,
+        config.probability_weight,
+        config.liquidity_weight,
+        config.iv_rank_weight,
+    ];
+    let total_weight: f64 = weights.iter().map(|weight| weight.abs()).sum();
+
+    let iv_ranks = iv_ranks(options);
+    let factors: Vec<[f64; FACTORS]> = options
+        .iter()
+        .zip(&iv_ranks)
+        .map(|(option, iv_rank)| {
+            [
+                option.metrics.return_on_capital,
+                option.prob_profit,
+                liquidity(option),
+                *iv_rank,
+            ]
+        })
+        .collect();
+
+    let ranges: Vec<(f64, f64)> = (0..FACTORS)
+        .map(|i| {
+            factors.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), raw| {
+                (low.min(raw[i]), high.max(raw[i]))
+            })
+        })
+        .collect();
+
+    for (option, raw) in options.iter_mut().zip(&factors) {
+        let weighted: f64 = (0..FACTORS)
+            .map(|i| weights[i] * normalize(raw[i], ranges[i]))
+            .sum();
+
+        option.score = Some(if total_weight > 0.0 {
+            weighted / total_weight * 100.0
+        } else {
+            0.0
+        });
+    }
+
+    options.sort_by(|a, b| b.score.unwrap_or(0.0).total_cmp(&a.score.unwrap_or(0.0)));
+}
+
+// Strategies score in their own units (yield, reward/risk, move ratio), so each strategy's
+// scores are rescaled to 0-100 across the cycle. Candidates stay grouped by strategy, in the
+// order the strategies ran, best first within each.
+pub fn rank_candidates(candidates: &mut [Candidate]) {
+    let mut ranges: HashMap<String, (f64, f64)> = HashMap::new();
+    let mut order: HashMap<String, usize> = HashMap::new();
+
+    for candidate in candidates.iter() {
+        let range = ranges
+            .entry(candidate.strategy.clone())
+            .or_insert((f64::INFINITY, f64::NEG_INFINITY));
+        *range = (range.0.min(candidate.score), range.1.max(candidate.score));
+
+        let next = order.len();
+        order.entry(candidate.strategy.clone()).or_insert(next);
+    }
+
+    for candidate in candidates.iter_mut() {
+        candidate.score = normalize(candidate.score, ranges[&candidate.strategy]) * 100.0;
+    }
+
+    candidates.sort_by(|a, b| {
+        order[&a.strategy]
+            .cmp(&order[&b.strategy])
+            .then(b.score.total_cmp(&a.score))
+    });
+}
+
+// Keeps the best `top_per_symbol` per symbol and `top_n` overall within each group (one
+// strategy's candidates), for items already ordered best first
+pub fn apply_limits<T>(
+    items: &mut Vec<T>,
+    config: &RankingConfig,
+    key: impl Fn(&T) -> (&str, &str), // (group, symbol)
+) {
+    let mut per_symbol: HashMap<(String, String), usize> = HashMap::new();
+    let mut per_group: HashMap<String, usize> = HashMap::new();
+
+    items.retain(|item| {
+        let (group, symbol) = key(item);
+        let symbol_count = per_symbol
+            .entry((group.to_string(), symbol.to_string()))
+            .or_default();
+        *symbol_count += 1;
+
+        if config.top_per_symbol > 0 && *symbol_count > config.top_per_symbol {
+            return false;
+        }
+
+        let group_count = per_group.entry(group.to_string()).or_default();
+        *group_count += 1;
+
+        config.top_n == 0 || *group_count <= config.top_n
+    });
+}
+
+// Min-max scaling to 0..1; a factor that doesn't vary sits in the middle
+fn normalize(value: f64, (low, high): (f64, f64)) -> f64 {
+    if high - low > f64::EPSILON {
+        (value - low) / (high - low)
+    } else {
+        0.5
+    }
+}
+
+// Log of traded and open contracts, discounted by the bid/ask spread (no quote counts as 100%)
+fn liquidity(option: &OptionChain) -> f64 {
+    let activity = (1.0 + option.volume.max(0) as f64 + option.open_interest.max(0) as f64).ln();
+    let spread = option.spread_percent().unwrap_or(100.0);
+
+    activity / (1.0 + spread / 100.0)
+}
+
+// Each option's IV percentile among the same symbol's options (0 cheapest, 1 richest). There
+// is no IV history to rank against, so the symbol's current candidates are the reference.
+fn iv_ranks(options: &[OptionChain]) -> Vec<f64> {
+    let mut by_symbol: HashMap<&str, Vec<f64>> = HashMap::new();
+
+    for option in options.iter().filter(|option| option.iv_status.is_usable()) {
+        by_symbol
+            .entry(option.symbol.as_str())
+            .or_default()
+            .push(option.implied_volatility);
+    }
+
+    options
+        .iter()
+        .map(|option| match by_symbol.get(option.symbol.as_str()) {
+            Some(ivs) if ivs.len() > 1 && option.iv_status.is_usable() => {
+                let cheaper = ivs.iter().filter(|&&iv| iv < option.implied_volatility).count();
+                cheaper as f64 / (ivs.len() - 1) as f64
+            }
+            _ => 0.5,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{OptionMetrics, OptionType};
+
+    fn option(symbol: &str, return_percent: f64, prob_profit: f64, iv: f64) -> OptionChain {
+        OptionChain {
+            symbol: symbol.to_string(),
+            implied_volatility: iv,
+            prob_profit,
+            metrics: OptionMetrics {
+                return_on_capital: return_percent,
+                ..OptionMetrics::default()
+            },
+            ..OptionChain::sample(OptionType::Call, 100.0, 2.0)
+        }
+    }
+
+    fn weights(return_weight: f64, probability_weight: f64, iv_rank_weight: f64) -> RankingConfig {
+        RankingConfig {
+            return_weight,
+            probability_weight,
+            liquidity_weight: 0.0,
+            iv_rank_weight,
+            ..RankingConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_weighted_normalized_score() {
+        let mut options = vec![
+            option("AAPL", 10.0, 0.6, 0.3),
+            option("AAPL", 50.0, 0.2, 0.3),
+            option("AAPL", 30.0, 0.4, 0.3),
+        ];
+
+        rank_options(&mut options, &weights(0.75, 0.25, 0.0));
+
+        let scores: Vec<f64> = options.iter().map(|o| o.score.unwrap()).collect();
+        assert!((scores[0] - 75.0).abs() < 1e-9); // best return, worst probability
+        assert!((scores[1] - 50.0).abs() < 1e-9);
+        assert!((scores[2] - 25.0).abs() < 1e-9);
+        assert_eq!(options[0].metrics.return_on_capital, 50.0);
+    }
+
+    #[test]
+    fn test_negative_iv_weight_prefers_cheap_volatility() {
+        let mut options = vec![option("TSLA", 20.0, 0.3, 0.9), option("TSLA", 20.0, 0.3, 0.5)];
+
+        rank_options(&mut options, &weights(0.5, 0.0, -0.5));
+
+        assert_eq!(options[0].implied_volatility, 0.5);
+        assert!(options[0].score.unwrap() > options[1].score.unwrap());
+    }
+
+    #[test]
+    fn test_top_per_symbol_and_top_n() {
+        let mut options = vec![
+            option("AAPL", 90.0, 0.5, 0.3),
+            option("AAPL", 80.0, 0.5, 0.3),
+            option("AAPL", 70.0, 0.5, 0.3),
+            option("MSFT", 60.0, 0.5, 0.3),
+            option("NVDA", 10.0, 0.5, 0.3),
+        ];
+        let config = RankingConfig {
+            top_n: 3,
+            top_per_symbol: 2,
+            ..weights(1.0, 0.0, 0.0)
+        };
+
+        rank_options(&mut options, &config);
+        apply_limits(&mut options, &config, |option| ("", option.symbol.as_str()));
+
+        let kept: Vec<f64> = options.iter().map(|o| o.metrics.return_on_capital).collect();
+        assert_eq!(kept, vec![90.0, 80.0, 60.0]);
+    }
+
+    fn candidate(strategy: &str, symbol: &str, score: f64) -> Candidate {
+        Candidate {
+            strategy: strategy.to_string(),
+            symbol: symbol.to_string(),
+            score,
+            ..Candidate::default()
+        }
+    }
+
+    #[test]
+    fn test_candidates_ranked_and_limited_per_strategy() {
+        let mut candidates = vec![
+            candidate("vertical_spreads", "AAPL", 0.5),
+            candidate("covered_calls", "AAPL", 12.0),
+            candidate("vertical_spreads", "AAPL", 2.5),
+            candidate("covered_calls", "MSFT", 30.0),
+            candidate("vertical_spreads", "MSFT", 1.5),
+            candidate("covered_calls", "AAPL", 20.0),
+        ];
+
+        rank_candidates(&mut candidates);
+
+        let ranked: Vec<(&str, f64)> = candidates
+            .iter()
+            .map(|c| (c.strategy.as_str(), c.score.round()))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                ("vertical_spreads", 100.0),
+                ("vertical_spreads", 50.0),
+                ("vertical_spreads", 0.0),
+                ("covered_calls", 100.0),
+                ("covered_calls", 44.0),
+                ("covered_calls", 0.0),
+            ]
+        );
+
+        let config = RankingConfig {
+            top_n: 2,
+            top_per_symbol: 1,
+            ..weights(1.0, 0.0, 0.0)
+        };
+        apply_limits(&mut candidates, &config, |c| (c.strategy.as_str(), c.symbol.as_str()));
+
+        let kept: Vec<(&str, &str)> = candidates
+            .iter()
+            .map(|c| (c.strategy.as_str(), c.symbol.as_str()))
+            .collect();
+        assert_eq!(
+            kept,
+            vec![
+                ("vertical_spreads", "AAPL"),
+                ("vertical_spreads", "MSFT"),
+                ("covered_calls", "MSFT"),
+                ("covered_calls", "AAPL"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,341 @@
+use crate::api::UnderlyingQuote;
+use crate::models::{FilterExpression, OptionChain, ScannerConfig, WatchlistEntry};
+use crate::scanner::calendar_spread::{CalendarSpread, CalendarSpreads};
+use crate::scanner::cash_secured_put::{CashSecuredPut, CashSecuredPuts};
+use crate::scanner::covered_call::{CoveredCall, CoveredCalls};
+use crate::scanner::neutral_strategies::{NeutralSetup, NeutralStrategies};
+use crate::scanner::vertical_spread::{VerticalSpread, VerticalSpreads};
+use chrono::{DateTime, Utc};
+
+pub const LONG_OPTIONS: &str = "long_options";
+pub const COVERED_CALLS: &str = "covered_calls";
+pub const CASH_SECURED_PUTS: &str = "cash_secured_puts";
+pub const VERTICAL_SPREADS: &str = "vertical_spreads";
+pub const NEUTRAL_STRATEGIES: &str = "neutral_strategies";
+pub const CALENDAR_SPREADS: &str = "calendar_spreads";
+
+// What a strategy sees for one symbol: the underlying quote, the watchlist entry (for
+// per-symbol overrides) and the priced chain, greeks, probabilities and costs included. The
+// chain is already narrowed by the contract filters unless the strategy opts out of them.
+pub struct StrategyInput<'a> {
+    pub quote: &'a UnderlyingQuote,
+    pub entry: &'a WatchlistEntry,
+    pub chains: &'a [OptionChain],
+}
+
+// One trade idea. `contract_ids` lists every leg; `metrics` are strategy-specific figures in
+// the order the strategy wants them shown. `detail` carries the built-in strategies' full
+// record, which gets its own table and CSV; anything else shares the candidate table.
+#[derive(Debug, Clone, Default)]
+pub struct Candidate {
+    pub timestamp: DateTime<Utc>,
+    pub strategy: String,
+    pub symbol: String,
+    pub score: f64,
+    pub contract_ids: Vec<String>,
+    pub metrics: Vec<(String, f64)>,
+    pub detail: CandidateDetail,
+}
+
+#[derive(Debug, Clone, Default)]
+pub enum CandidateDetail {
+    #[default]
+    Generic,
+    LongOption(Box<OptionChain>),
+    CoveredCall(CoveredCall),
+    CashSecuredPut(CashSecuredPut),
+    VerticalSpread(VerticalSpread),
+    Neutral(NeutralSetup),
+    Calendar(CalendarSpread),
+}
+
+impl Candidate {
+    pub fn metric(&self, name: &str) -> Option<f64> {
+        self.metrics
+            .iter()
+            .find(|(metric, _)| metric == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+// Scores one symbol's chain. Implementations are shared across concurrent symbol scans.
+pub trait Strategy: Send + Sync {
+    fn name(&self) -> &str;
+    fn evaluate(&self, input: &StrategyInput) -> Vec<Candidate>;
+
+    // Multi-leg strategies return false to see every priced contract, illiquid wings included
+    fn uses_contract_filters(&self) -> bool {
+        true
+    }
+}
+
+// Strategies by name, in registration order
+#[derive(Default)]
+pub struct StrategyRegistry {
+    strategies: Vec<Box<dyn Strategy>>,
+}
+
+impl StrategyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn builtin(config: &ScannerConfig) -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(LongOptions::from_config(config)));
+        registry.register(Box::new(CoveredCalls(config.covered_calls.clone())));
+        registry.register(Box::new(CashSecuredPuts(config.cash_secured_puts.clone())));
+        registry.register(Box::new(VerticalSpreads(config.vertical_spreads.clone())));
+        registry.register(Box::new(NeutralStrategies(config.neutral_strategies.clone())));
+        registry.register(Box::new(CalendarSpreads(config.calendar_spreads.clone())));
+        registry
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.strategies.iter().any(|strategy| strategy.name() == name)
+    }
+
+    // A strategy registered under an existing name replaces it
+    pub fn register(&mut self, strategy: Box<dyn Strategy>) {
+        self.strategies.retain(|existing| existing.name() != strategy.name());
+        self.strategies.push(strategy);
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.strategies.iter().map(|strategy| strategy.name()).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Strategy> {
+        self.strategies.iter().map(|strategy| strategy.as_ref())
+    }
+
+    // Keeps only the named strategies, in the order given, ignoring repeats; the error is the
+    // first unknown name
+    pub fn select(mut self, names: &[String]) -> Result<Self, String> {
+        let mut selected: Vec<Box<dyn Strategy>> = Vec::with_capacity(names.len());
+
+        for name in names {
+            if selected.iter().any(|strategy| strategy.name() == name) {
+                continue;
+            }
+
+            let position = self
+                .strategies
+                .iter()
+                .position(|strategy| strategy.name() == name)
+                .ok_or_else(|| name.clone())?;
+            selected.push(self.strategies.remove(position));
+        }
+
+        Ok(Self { strategies: selected })
+    }
+}
+
+// Buy a single call or put: the scanner's original profit-percent scoring, with the
+// probability and expected value floors. A filter expression replaces the profit threshold.
+pub struct LongOptions {
+    pub filter: Option<FilterExpression>,
+    pub min_profit_percent: f64,
+    pub min_prob_itm: f64,
+    pub min_prob_touch: f64,
+    pub min_expected_value: Option<f64>,
+}
+
+impl LongOptions {
+    pub fn from_config(config: &ScannerConfig) -> Self {
+        Self {
+            filter: config.filter_expression.clone(),
+            min_profit_percent: config.min_profit_percent,
+            min_prob_itm: config.min_prob_itm,
+            min_prob_touch: config.min_prob_touch,
+            min_expected_value: config.min_expected_value,
+        }
+    }
+
+    fn qualifies(&self, chain: &OptionChain, min_profit_percent: f64) -> bool {
+        let selected = match &self.filter {
+            Some(filter) => filter.matches(chain),
+            None => chain.profit_percent >= min_profit_percent,
+        };
+
+        selected
+            && chain.prob_itm >= self.min_prob_itm
+            && chain.prob_touch >= self.min_prob_touch
+            && self
+                .min_expected_value
+                .is_none_or(|min| chain.expected_value >= min)
+    }
+}
+
+impl Strategy for LongOptions {
+    fn name(&self) -> &str {
+        LONG_OPTIONS
+    }
+
+    fn evaluate(&self, input: &StrategyInput) -> Vec<Candidate> {
+        let min_profit_percent = input.entry.min_profit_percent.unwrap_or(self.min_profit_percent);
+
+        input
+            .chains
+            .iter()
+            .filter(|chain| self.qualifies(chain, min_profit_percent))
+            .map(|chain| Candidate {
+                timestamp: chain.timestamp,
+                strategy: LONG_OPTIONS.to_string(),
+                symbol: chain.symbol.clone(),
+                score: chain.profit_percent,
+                contract_ids: vec![chain.contract_id.clone()],
+                metrics: vec![
+                    ("profit_percent".to_string(), chain.profit_percent),
+                    ("annualized_return".to_string(), chain.metrics.annualized_return),
+                    ("prob_profit".to_string(), chain.prob_profit),
+                    ("expected_value".to_string(), chain.expected_value),
+                ],
+                detail: CandidateDetail::LongOption(Box::new(chain.clone())),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::OptionType;
+
+    fn chain(strike: f64, profit_percent: f64, prob_itm: f64) -> OptionChain {
+        OptionChain {
+            profit_percent,
+            prob_itm,
+            ..OptionChain::sample(OptionType::Call, strike, 2.0)
+        }
+    }
+
+    fn quote() -> UnderlyingQuote {
+        UnderlyingQuote {
+            symbol: "AAPL".to_string(),
+            conid: 265598,
+            price: 100.0,
+            dividend_amount: None,
+            ex_dividend_date: None,
+        }
+    }
+
+    fn long_options() -> LongOptions {
+        LongOptions {
+            filter: None,
+            min_profit_percent: 5.0,
+            min_prob_itm: 0.2,
+            min_prob_touch: 0.0,
+            min_expected_value: None,
+        }
+    }
+
+    struct Fixed(&'static str);
+
+    impl Strategy for Fixed {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn evaluate(&self, _input: &StrategyInput) -> Vec<Candidate> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_long_options_thresholds_and_symbol_override() {
+        let chains = [chain(95.0, 8.0, 0.6), chain(100.0, 4.0, 0.5), chain(110.0, 9.0, 0.1)];
+        let quote = quote();
+        let entry = WatchlistEntry::new("AAPL");
+        let input = StrategyInput {
+            quote: &quote,
+            entry: &entry,
+            chains: &chains,
+        };
+
+        let candidates = long_options().evaluate(&input);
+
+        // 100 misses the profit floor, 110 the probability floor
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].contract_ids, vec!["20250615C95".to_string()]);
+        assert_eq!(candidates[0].score, 8.0);
+        assert_eq!(candidates[0].metric("profit_percent"), Some(8.0));
+
+        let lenient = WatchlistEntry {
+            min_profit_percent: Some(3.0),
+            ..entry.clone()
+        };
+        let input = StrategyInput {
+            entry: &lenient,
+            ..input
+        };
+        assert_eq!(long_options().evaluate(&input).len(), 2);
+    }
+
+    #[test]
+    fn test_filter_expression_replaces_profit_threshold() {
+        let chains = [chain(95.0, 8.0, 0.6), chain(100.0, 4.0, 0.5)];
+        let quote = quote();
+        let entry = WatchlistEntry::new("AAPL");
+        let input = StrategyInput {
+            quote: &quote,
+            entry: &entry,
+            chains: &chains,
+        };
+        let strategy = LongOptions {
+            filter: Some("strike >= 100".parse().unwrap()),
+            ..long_options()
+        };
+
+        let candidates = strategy.evaluate(&input);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].contract_ids, vec!["20250615C100".to_string()]);
+    }
+
+    #[test]
+    fn test_builtin_registers_every_strategy() {
+        let config = ScannerConfig::from_env().unwrap();
+        let registry = StrategyRegistry::builtin(&config);
+
+        assert_eq!(
+            registry.names(),
+            vec![
+                LONG_OPTIONS,
+                COVERED_CALLS,
+                CASH_SECURED_PUTS,
+                VERTICAL_SPREADS,
+                NEUTRAL_STRATEGIES,
+                CALENDAR_SPREADS
+            ]
+        );
+
+        let filtered: Vec<&str> = registry
+            .iter()
+            .filter(|strategy| strategy.uses_contract_filters())
+            .map(|strategy| strategy.name())
+            .collect();
+        assert_eq!(filtered, vec![LONG_OPTIONS, COVERED_CALLS, CASH_SECURED_PUTS]);
+
+        let selected = registry.select(&[CALENDAR_SPREADS.to_string()]).unwrap();
+        assert_eq!(selected.names(), vec![CALENDAR_SPREADS]);
+    }
+
+    #[test]
+    fn test_registry_select() {
+        let mut registry = StrategyRegistry::new();
+        registry.register(Box::new(Fixed("a")));
+        registry.register(Box::new(Fixed("b")));
+        registry.register(Box::new(Fixed("a")));
+
+        assert_eq!(registry.names(), vec!["b", "a"]);
+
+        let names = ["a".to_string(), "b".to_string(), "a".to_string()];
+        let selected = registry.select(&names).unwrap();
+        assert_eq!(selected.names(), vec!["a", "b"]);
+        assert!(selected.contains("b"));
+
+        let error = selected.select(&["c".to_string()]).err();
+        assert_eq!(error, Some("c".to_string()));
+    }
+}
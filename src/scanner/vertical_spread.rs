@@ -0,0 +1,313 @@
+use crate::models::{OptionChain, OptionType, VerticalSpreadConfig};
+use crate::scanner::strategy::{
+    Candidate, CandidateDetail, Strategy, StrategyInput, VERTICAL_SPREADS,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum SpreadKind {
+    BullCall,
+    BearCall,
+    BullPut,
+    BearPut,
+}
+
+impl SpreadKind {
+    pub fn is_credit(&self) -> bool {
+        matches!(self, SpreadKind::BearCall | SpreadKind::BullPut)
+    }
+}
+
+impl fmt::Display for SpreadKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpreadKind::BullCall => write!(f, "Bull Call"),
+            SpreadKind::BearCall => write!(f, "Bear Call"),
+            SpreadKind::BullPut => write!(f, "Bull Put"),
+            SpreadKind::BearPut => write!(f, "Bear Put"),
+        }
+    }
+}
+
+// One long and one short leg in the same expiry. `net_premium` is per share (paid for debit
+// spreads, received for credit spreads) and, like everything else here, net of trading costs;
+// max profit and loss are per spread.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerticalSpread {
+    pub timestamp: DateTime<Utc>,
+    pub symbol: String,
+    pub kind: SpreadKind,
+    pub expiration: DateTime<Utc>,
+    pub long_strike: f64,
+    pub short_strike: f64,
+    pub width: f64,
+    pub net_premium: f64,
+    pub max_profit: f64,
+    pub max_loss: f64,
+    pub breakeven: f64,
+    pub reward_risk: f64,
+    pub stock_price: f64,
+    pub long_contract_id: String,
+    pub short_contract_id: String,
+}
+
+// Every same-expiry strike pair no wider than `max_width`, keeping the best `top_per_symbol`
+// by reward/risk. Credit spreads must collect at least `min_credit` per share.
+pub fn evaluate_vertical_spreads(
+    chains: &[OptionChain],
+    limits: &VerticalSpreadConfig,
+) -> Vec<VerticalSpread> {
+    let mut legs: Vec<&OptionChain> = chains
+        .iter()
+        .filter(|chain| chain.option_price > 0.0)
+        .collect();
+    legs.sort_by(|a, b| {
+        (a.expiration, a.option_type == OptionType::Put)
+            .cmp(&(b.expiration, b.option_type == OptionType::Put))
+            .then(a.strike.total_cmp(&b.strike))
+    });
+
+    let mut spreads = Vec::new();
+
+    for (i, lower) in legs.iter().enumerate() {
+        for upper in &legs[i + 1..] {
+            if upper.expiration != lower.expiration || upper.option_type != lower.option_type {
+                break;
+            }
+
+            let width = upper.strike - lower.strike;
+            if width <= 0.0 {
+                continue;
+            }
+            if width > limits.max_width {
+                break;
+            }
+
+            let pairs = match lower.option_type {
+                OptionType::Call => [
+                    (SpreadKind::BullCall, *lower, *upper),
+                    (SpreadKind::BearCall, *upper, *lower),
+                ],
+                OptionType::Put => [
+                    (SpreadKind::BearPut, *upper, *lower),
+                    (SpreadKind::BullPut, *lower, *upper),
+                ],
+            };
+
+            spreads.extend(
+                pairs
+                    .into_iter()
+                    .filter_map(|(kind, long, short)| vertical_spread(kind, long, short))
+                    .filter(|spread| {
+                        !spread.kind.is_credit() || spread.net_premium >= limits.min_credit
+                    }),
+            );
+        }
+    }
+
+    spreads.sort_by(|a, b| b.reward_risk.total_cmp(&a.reward_risk));
+    spreads.truncate(limits.top_per_symbol);
+    spreads
+}
+
+fn vertical_spread(
+    kind: SpreadKind,
+    long: &OptionChain,
+    short: &OptionChain,
+) -> Option<VerticalSpread> {
+    let multiplier = long.multiplier.max(1.0);
+    let width = (short.strike - long.strike).abs();
+
+    let long_cost = long.cost_basis();
+    let debit = long_cost - short.short_proceeds();
+
+    let (net_premium, max_profit, max_loss, breakeven) = match kind {
+        SpreadKind::BullCall => (debit, width - debit, debit, long.strike + debit),
+        SpreadKind::BearPut => (debit, width - debit, debit, long.strike - debit),
+        SpreadKind::BearCall => (-debit, -debit, width + debit, short.strike - debit),
+        SpreadKind::BullPut => (-debit, -debit, width + debit, short.strike + debit),
+    };
+
+    // A spread that cannot lose (or cannot win) means stale or crossed quotes
+    if max_profit <= 0.0 || max_loss <= 0.0 {
+        return None;
+    }
+
+    Some(VerticalSpread {
+        timestamp: long.timestamp,
+        symbol: long.symbol.clone(),
+        kind,
+        expiration: long.expiration,
+        long_strike: long.strike,
+        short_strike: short.strike,
+        width,
+        net_premium,
+        max_profit: max_profit * multiplier,
+        max_loss: max_loss * multiplier,
+        breakeven,
+        reward_risk: max_profit / max_loss,
+        stock_price: long.stock_price,
+        long_contract_id: long.contract_id.clone(),
+        short_contract_id: short.contract_id.clone(),
+    })
+}
+
+// Width and credit limits can be overridden per symbol from the watchlist
+pub struct VerticalSpreads(pub VerticalSpreadConfig);
+
+impl Strategy for VerticalSpreads {
+    fn name(&self) -> &str {
+        VERTICAL_SPREADS
+    }
+
+    // The long leg of a credit spread is usually far out of the money and thinly traded
+    fn uses_contract_filters(&self) -> bool {
+        false
+    }
+
+    fn evaluate(&self, input: &StrategyInput) -> Vec<Candidate> {
+        let limits = VerticalSpreadConfig {
+            max_width: input.entry.spread_width.unwrap_or(self.0.max_width),
+            min_credit: input.entry.min_credit.unwrap_or(self.0.min_credit),
+            ..self.0.clone()
+        };
+
+        evaluate_vertical_spreads(input.chains, &limits)
+            .into_iter()
+            .map(|spread| Candidate {
+                timestamp: spread.timestamp,
+                strategy: VERTICAL_SPREADS.to_string(),
+                symbol: spread.symbol.clone(),
+                score: spread.reward_risk,
+                contract_ids: vec![
+                    spread.long_contract_id.clone(),
+                    spread.short_contract_id.clone(),
+                ],
+                metrics: vec![
+                    ("net_premium".to_string(), spread.net_premium),
+                    ("max_profit".to_string(), spread.max_profit),
+                    ("max_loss".to_string(), spread.max_loss),
+                    ("reward_risk".to_string(), spread.reward_risk),
+                ],
+                detail: CandidateDetail::VerticalSpread(spread),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(
+        spreads: &[VerticalSpread],
+        kind: SpreadKind,
+        long: f64,
+        short: f64,
+    ) -> &VerticalSpread {
+        spreads
+            .iter()
+            .find(|s| s.kind == kind && s.long_strike == long && s.short_strike == short)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_call_spreads() {
+        let chains = [
+            OptionChain::sample(OptionType::Call, 100.0, 5.0),
+            OptionChain::sample(OptionType::Call, 105.0, 3.0),
+        ];
+        let spreads = evaluate_vertical_spreads(&chains, &VerticalSpreadConfig::default());
+
+        let bull = find(&spreads, SpreadKind::BullCall, 100.0, 105.0);
+        assert_eq!(bull.net_premium, 2.0);
+        assert_eq!(bull.max_profit, 300.0);
+        assert_eq!(bull.max_loss, 200.0);
+        assert_eq!(bull.breakeven, 102.0);
+        assert_eq!(bull.reward_risk, 1.5);
+
+        let bear = find(&spreads, SpreadKind::BearCall, 105.0, 100.0);
+        assert_eq!(bear.net_premium, 2.0);
+        assert_eq!(bear.max_profit, 200.0);
+        assert_eq!(bear.max_loss, 300.0);
+        assert_eq!(bear.breakeven, 102.0);
+    }
+
+    #[test]
+    fn test_put_spreads() {
+        let chains = [
+            OptionChain::sample(OptionType::Put, 95.0, 2.0),
+            OptionChain::sample(OptionType::Put, 100.0, 4.5),
+        ];
+        let spreads = evaluate_vertical_spreads(&chains, &VerticalSpreadConfig::default());
+
+        let bull = find(&spreads, SpreadKind::BullPut, 95.0, 100.0);
+        assert_eq!(bull.net_premium, 2.5);
+        assert_eq!(bull.max_loss, 250.0);
+        assert_eq!(bull.breakeven, 97.5);
+
+        let bear = find(&spreads, SpreadKind::BearPut, 100.0, 95.0);
+        assert_eq!(bear.net_premium, 2.5);
+        assert_eq!(bear.max_profit, 250.0);
+        assert_eq!(bear.breakeven, 97.5);
+    }
+
+    #[test]
+    fn test_width_and_min_credit_limits() {
+        let chains = [
+            OptionChain::sample(OptionType::Call, 100.0, 5.0),
+            OptionChain::sample(OptionType::Call, 105.0, 3.0),
+            OptionChain::sample(OptionType::Call, 120.0, 0.5),
+        ];
+        let limits = VerticalSpreadConfig {
+            max_width: 5.0,
+            min_credit: 2.5,
+            ..VerticalSpreadConfig::default()
+        };
+
+        let spreads = evaluate_vertical_spreads(&chains, &limits);
+
+        // Only the 100/105 pair is narrow enough, and its 2.00 credit is below the minimum
+        assert_eq!(spreads.len(), 1);
+        assert_eq!(spreads[0].kind, SpreadKind::BullCall);
+    }
+
+    #[test]
+    fn test_strategy_applies_symbol_limits() {
+        let chains = [
+            OptionChain::sample(OptionType::Call, 100.0, 5.0),
+            OptionChain::sample(OptionType::Call, 105.0, 3.0),
+            OptionChain::sample(OptionType::Call, 120.0, 0.5),
+        ];
+        let quote = crate::api::UnderlyingQuote {
+            symbol: "NVDA".to_string(),
+            conid: 4815747,
+            price: 100.0,
+            dividend_amount: None,
+            ex_dividend_date: None,
+        };
+        let entry = crate::models::WatchlistEntry {
+            spread_width: Some(5.0),
+            min_credit: Some(2.5),
+            ..crate::models::WatchlistEntry::new("NVDA")
+        };
+        let input = StrategyInput {
+            quote: &quote,
+            entry: &entry,
+            chains: &chains,
+        };
+
+        let candidates = VerticalSpreads(VerticalSpreadConfig::default()).evaluate(&input);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].strategy, VERTICAL_SPREADS);
+        assert_eq!(candidates[0].contract_ids, vec!["20250615C100", "20250615C105"]);
+        assert_eq!(candidates[0].score, 1.5);
+        assert!(matches!(
+            &candidates[0].detail,
+            CandidateDetail::VerticalSpread(spread) if spread.kind == SpreadKind::BullCall
+        ));
+    }
+}
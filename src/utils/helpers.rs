@@ -1,7 +1,26 @@
-use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+// RFC 3986 unreserved characters stay as-is so class shares like BRK-B survive
+const SYMBOL_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
 
 pub fn encode_symbol(symbol: &str) -> String {
-    utf8_percent_encode(symbol, NON_ALPHANUMERIC).to_string()
+    utf8_percent_encode(symbol, SYMBOL_ENCODE_SET).to_string()
+}
+
+// Stars out all but the last `visible` characters, e.g. account IDs in printed config
+pub fn mask(value: &str, visible: usize) -> String {
+    let count = value.chars().count();
+    let hidden = count.saturating_sub(visible).max(count.min(1));
+
+    value
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i < hidden { '*' } else { c })
+        .collect()
 }
 
 pub fn format_currency(amount: f64) -> String {
@@ -22,6 +41,20 @@ mod tests {
         assert_eq!(encode_symbol("BRK-B"), "BRK-B");
     }
 
+    #[test]
+    fn test_encode_symbol_escapes_reserved_characters() {
+        assert_eq!(encode_symbol("BRK.B"), "BRK.B");
+        assert_eq!(encode_symbol("BF/B"), "BF%2FB");
+        assert_eq!(encode_symbol("A&B C"), "A%26B%20C");
+    }
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(mask("U1234567", 3), "*****567");
+        assert_eq!(mask("U12", 3), "*12");
+        assert_eq!(mask("", 3), "");
+    }
+
     #[test]
     fn test_format_currency() {
         assert_eq!(format_currency(123.456), "$123.46");
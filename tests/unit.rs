@@ -0,0 +1,5 @@
+// Public API tests; each file under tests/unit is one module
+mod unit {
+    mod test_models;
+    mod test_profit_calculator;
+}
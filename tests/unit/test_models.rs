@@ -14,7 +14,7 @@ fn test_option_type_display() {
 
 #[test]
 fn test_option_chain_creation() {
-    use ibkr_nasdaq_scanner::models::{OptionChain, OptionType};
+    use ibkr_nasdaq_scanner::models::{IvStatus, OptionChain, OptionType};
     use chrono::{TimeZone, Utc};
     
     let expiration = Utc.with_ymd_and_hms(2024, 12, 20, 0, 0, 0).unwrap();
@@ -25,15 +25,17 @@ fn test_option_chain_creation() {
         expiration,
         strike: 180.0,
         option_price: 2.50,
+        bid: 2.45,
+        ask: 2.55,
         stock_price: 185.0,
         profit_percent: 20.0,
-        volume: 1000,
-        implied_volatility: 0.25,
-        timestamp: Utc::now(),
         contract_id: "123456".to_string(),
+        ..OptionChain::default()
     };
     
     assert_eq!(chain.symbol, "AAPL");
     assert_eq!(chain.strike, 180.0);
     assert_eq!(chain.profit_percent, 20.0);
+    assert_eq!(chain.multiplier, 100.0);
+    assert_eq!(chain.iv_status, IvStatus::Unknown);
 }